@@ -0,0 +1,9 @@
+override MULTIPLIER: f32 = 1.0;
+
+@group(0) @binding(0)
+var<storage, read_write> values: array<f32>;
+
+@compute @workgroup_size(1)
+fn main(@builtin(global_invocation_id) invocation_id: vec3<u32>) {
+    values[invocation_id.x] = values[invocation_id.x] * MULTIPLIER;
+}
@@ -0,0 +1,8 @@
+@group(0) @binding(0)
+var<storage, read_write> invocations: atomic<u32>;
+
+@compute @workgroup_size(4, 2)
+fn main(@builtin(global_invocation_id) invocation_id: vec3<u32>) {
+    // Number of invocations along x, the same for every run
+    atomicMax(&invocations, invocation_id.x + 1u);
+}
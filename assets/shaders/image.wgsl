@@ -0,0 +1,23 @@
+@group(0) @binding(0)
+var<uniform> time: f32;
+
+@group(0) @binding(1)
+var output: texture_storage_2d<rgba8unorm, write>;
+
+@compute @workgroup_size(8, 8, 1)
+fn main(@builtin(global_invocation_id) invocation_id: vec3<u32>) {
+    let size = textureDimensions(output);
+    if (invocation_id.x >= size.x || invocation_id.y >= size.y) {
+        return;
+    }
+
+    let uv = vec2<f32>(invocation_id.xy) / vec2<f32>(size);
+    let color = vec4<f32>(
+        0.5 + 0.5 * sin(time + uv.x * 6.28),
+        uv.y,
+        0.5 + 0.5 * cos(time + uv.y * 6.28),
+        1.0,
+    );
+
+    textureStore(output, vec2<i32>(invocation_id.xy), color);
+}
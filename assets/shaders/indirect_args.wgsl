@@ -0,0 +1,12 @@
+@group(0) @binding(0)
+var<uniform> count: u32;
+
+@group(0) @binding(1)
+var<storage, read_write> args: array<u32>;
+
+@compute @workgroup_size(1)
+fn main() {
+    args[0] = count;
+    args[1] = 1u;
+    args[2] = 1u;
+}
@@ -0,0 +1,12 @@
+@group(0) @binding(0)
+var input: texture_2d<u32>;
+
+@group(0) @binding(1)
+var output: texture_storage_2d<r32uint, write>;
+
+@compute @workgroup_size(1)
+fn main(@builtin(global_invocation_id) invocation_id: vec3<u32>) {
+    let location = vec2<i32>(invocation_id.xy);
+    let value = textureLoad(input, location, 0).r;
+    textureStore(output, location, vec4<u32>(value * 2u, 0u, 0u, 0u));
+}
@@ -67,8 +67,8 @@ impl ComputeWorker for BoidWorker {
             .add_uniform("params", &params)
             .add_staging("boids_src", &initial_boids_data)
             .add_staging("boids_dst", &initial_boids_data)
-            .add_pass::<BoidsShader>(
-                [NUM_BOIDS / 64, 1, 1],
+            .add_pass_for_elements::<BoidsShader>(
+                [NUM_BOIDS, 1, 1],
                 &["params", "boids_src", "boids_dst"],
             )
             .add_swap("boids_src", "boids_dst")
@@ -0,0 +1,74 @@
+//! Example showing how to write into an `Image` asset from a compute shader and display it,
+//! without copying the data back to the CPU.
+
+use bevy::{
+    asset::RenderAssetUsages,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use bevy_app_compute::prelude::*;
+
+const SIZE: u32 = 256;
+
+#[derive(TypePath)]
+struct ImageShader;
+
+impl ComputeShader for ImageShader {
+    fn shader() -> ShaderRef {
+        "shaders/image.wgsl".into()
+    }
+}
+
+#[derive(Resource)]
+struct ImageComputeWorker;
+
+impl ComputeWorker for ImageComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        let image = Image::new_fill(
+            Extent3d {
+                width: SIZE,
+                height: SIZE,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 255],
+            // Storage textures can't use sRGB formats
+            TextureFormat::Rgba8Unorm,
+            RenderAssetUsages::RENDER_WORLD,
+        );
+
+        AppComputeWorkerBuilder::new(world)
+            .add_uniform("time", &0f32)
+            .add_new_image("image", image)
+            .add_pass_for_elements::<ImageShader>([SIZE, SIZE, 1], &["time", "image"])
+            .build()
+    }
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(AppComputePlugin)
+        .add_plugins(AppComputeWorkerPlugin::<ImageComputeWorker>::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, update_time)
+        .run();
+}
+
+fn setup(mut commands: Commands, compute_worker: Res<AppComputeWorker<ImageComputeWorker>>) {
+    commands.spawn(Camera2d);
+
+    // The image is displayed like any other, the compute shader writes directly into it.
+    commands.spawn((
+        ImageNode::new(compute_worker.get_image("image").unwrap().clone()),
+        Node {
+            width: Val::Px(SIZE as f32 * 2.),
+            height: Val::Px(SIZE as f32 * 2.),
+            ..default()
+        },
+    ));
+}
+
+fn update_time(time: Res<Time>, mut compute_worker: ResMut<AppComputeWorker<ImageComputeWorker>>) {
+    compute_worker.write("time", &time.elapsed_secs());
+}
@@ -0,0 +1,70 @@
+use bevy::{
+    platform::collections::HashMap,
+    render::render_resource::{BindGroup, BufferId, ComputePipelineId, SamplerId, TextureViewId},
+};
+
+/// Maximum number of bind groups kept per pass.
+///
+/// Passes reading swapped buffers alternate between bind groups, keeping a few
+/// of them avoids recreating one every run.
+const MAX_BIND_GROUPS_PER_PASS: usize = 4;
+
+/// Identifies a GPU resource bound to a pass.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum ResourceId {
+    Buffer(BufferId),
+    TextureView(TextureViewId),
+    Sampler(SamplerId),
+}
+
+/// Everything a bind group depends on, a bind group can be reused as long as its key is unchanged.
+///
+/// Swapped, resized or replaced resources get new ids, as do recompiled pipelines.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct BindGroupKey {
+    pub(crate) pipeline: ComputePipelineId,
+    pub(crate) resources: Vec<ResourceId>,
+}
+
+/// Bind groups of the compute passes of a worker, indexed by step and group.
+#[derive(Default)]
+pub(crate) struct BindGroupCache(HashMap<(usize, u32), Vec<(BindGroupKey, BindGroup)>>);
+
+impl BindGroupCache {
+    /// Bind group created for `group` of the step at `index` with `key`, if any.
+    pub(crate) fn get(&self, index: usize, group: u32, key: &BindGroupKey) -> Option<&BindGroup> {
+        self.0
+            .get(&(index, group))?
+            .iter()
+            .find(|(cached_key, _)| cached_key == key)
+            .map(|(_, bind_group)| bind_group)
+    }
+
+    /// Cache a new bind group for `group` of the step at `index`, evicting the oldest one if needed.
+    pub(crate) fn insert(
+        &mut self,
+        index: usize,
+        group: u32,
+        key: BindGroupKey,
+        bind_group: BindGroup,
+    ) {
+        let bind_groups = self.0.entry((index, group)).or_default();
+        if bind_groups.len() >= MAX_BIND_GROUPS_PER_PASS {
+            bind_groups.remove(0);
+        }
+
+        bind_groups.push((key, bind_group));
+    }
+
+    /// Drop every cached bind group.
+    pub(crate) fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Drop the cached bind groups using `resource`.
+    pub(crate) fn remove_resource(&mut self, resource: ResourceId) {
+        for bind_groups in self.0.values_mut() {
+            bind_groups.retain(|(key, _)| !key.resources.contains(&resource));
+        }
+    }
+}
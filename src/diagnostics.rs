@@ -0,0 +1,105 @@
+use std::{any::type_name, marker::PhantomData, time::Duration};
+
+use bevy::{
+    app::App,
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+    prelude::ResMut,
+};
+
+use crate::{traits::ComputeWorker, worker::AppComputeWorker};
+
+/// Paths of the [diagnostics](bevy::diagnostic) measured for the worker `W`, registered
+/// by [`AppComputeWorkerPlugin`](crate::prelude::AppComputeWorkerPlugin).
+///
+/// Paths are prefixed with `compute/` followed by the type name of `W`, and show up
+/// in [`LogDiagnosticsPlugin`](bevy::diagnostic::LogDiagnosticsPlugin) like any other.
+pub struct ComputeWorkerDiagnostics<W: ComputeWorker>(PhantomData<W>);
+
+impl<W: ComputeWorker> ComputeWorkerDiagnostics<W> {
+    /// GPU time of each finished run, in milliseconds.
+    ///
+    /// Sums the timestamps of every pass, so it's only measured for workers built with
+    /// [`profile`](crate::prelude::AppComputeWorkerBuilder::profile) on devices supporting
+    /// them.
+    pub fn gpu_time() -> DiagnosticPath {
+        Self::path("gpu_time")
+    }
+
+    /// Time between submitting each finished run and the GPU completing it, measured
+    /// on the CPU, in milliseconds.
+    pub fn submit_to_complete() -> DiagnosticPath {
+        Self::path("submit_to_complete")
+    }
+
+    /// CPU time spent recording each run, in milliseconds.
+    pub fn encode_time() -> DiagnosticPath {
+        Self::path("encode_time")
+    }
+
+    /// Bytes written to the buffers and textures of the worker each frame.
+    pub fn bytes_uploaded() -> DiagnosticPath {
+        Self::path("bytes_uploaded")
+    }
+
+    /// Bytes read back by the staging buffers of the worker each frame.
+    pub fn bytes_read_back() -> DiagnosticPath {
+        Self::path("bytes_read_back")
+    }
+
+    /// 1 on frames during which the worker waited for its runs synchronously, after
+    /// they ran asynchronously for longer than the maximum set with
+    /// [`asynchronous`](crate::prelude::AppComputeWorkerBuilder::asynchronous), 0 otherwise.
+    pub fn sync_fallbacks() -> DiagnosticPath {
+        Self::path("sync_fallbacks")
+    }
+
+    fn path(name: &str) -> DiagnosticPath {
+        DiagnosticPath::new(format!("compute/{}/{name}", type_name::<W>()))
+    }
+
+    /// Register every diagnostic of `W`.
+    pub(crate) fn register(app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::gpu_time()).with_suffix("ms"))
+            .register_diagnostic(Diagnostic::new(Self::submit_to_complete()).with_suffix("ms"))
+            .register_diagnostic(Diagnostic::new(Self::encode_time()).with_suffix("ms"))
+            .register_diagnostic(Diagnostic::new(Self::bytes_uploaded()).with_suffix("B"))
+            .register_diagnostic(Diagnostic::new(Self::bytes_read_back()).with_suffix("B"))
+            .register_diagnostic(Diagnostic::new(Self::sync_fallbacks()));
+    }
+
+    /// Measure what the worker did this frame.
+    pub(crate) fn measure(mut worker: ResMut<AppComputeWorker<W>>, mut diagnostics: Diagnostics) {
+        let stats = std::mem::take(&mut worker.stats);
+
+        if let Some(gpu_time) = stats.gpu_time {
+            diagnostics.add_measurement(&Self::gpu_time(), || millis(gpu_time));
+        }
+        if let Some(submit_to_complete) = stats.submit_to_complete {
+            diagnostics.add_measurement(&Self::submit_to_complete(), || millis(submit_to_complete));
+        }
+        if let Some(encode_time) = stats.encode_time {
+            diagnostics.add_measurement(&Self::encode_time(), || millis(encode_time));
+        }
+        diagnostics.add_measurement(&Self::bytes_uploaded(), || stats.bytes_uploaded as f64);
+        diagnostics.add_measurement(&Self::bytes_read_back(), || stats.bytes_read_back as f64);
+        diagnostics.add_measurement(&Self::sync_fallbacks(), || f64::from(stats.sync_fallbacks));
+    }
+}
+
+/// What a worker did during the current frame, measured at the end of it.
+#[derive(Debug, Default)]
+pub(crate) struct FrameStats {
+    /// GPU time of the most recently finished run, if its passes were timed.
+    pub(crate) gpu_time: Option<Duration>,
+    /// Time between submitting the most recently finished run and its completion.
+    pub(crate) submit_to_complete: Option<Duration>,
+    /// Time spent recording the most recently submitted run.
+    pub(crate) encode_time: Option<Duration>,
+    pub(crate) bytes_uploaded: u64,
+    pub(crate) bytes_read_back: u64,
+    pub(crate) sync_fallbacks: u32,
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.
+}
@@ -1,28 +1,399 @@
+use std::ops::Range;
+
+use wgpu::{BufferUsages, COPY_BUFFER_ALIGNMENT, TextureFormat};
+
+use crate::reflection::BindingKind;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Debug)]
 pub enum Error {
     BufferNotFound(String),
+    OutOfBounds {
+        name: String,
+        start: u64,
+        end: u64,
+        available: Range<u64>,
+    },
+    UnalignedWrite {
+        name: String,
+        start: u64,
+        end: u64,
+    },
+    RangeLengthMismatch {
+        name: String,
+        range: usize,
+        data: usize,
+    },
     StagingBufferNotFound(String),
+    StagingBufferNotReady(String),
+    StagingBufferMapFailed {
+        name: String,
+        message: String,
+    },
+    TextureNotFound(String),
+    ImageNotReady(String),
     InvalidStep(String),
+    StepNotFound(String),
+    DuplicateLabel(String),
+    DuplicateName(String),
+    Encoding {
+        name: String,
+        message: String,
+    },
+    ResourceInUse {
+        name: String,
+        step: String,
+    },
+    UnknownResource {
+        step: String,
+        name: String,
+    },
+    MissingShader(String),
     PipelinesEmpty,
     PipelineNotReady,
+    PipelineFailed {
+        shader: String,
+        message: String,
+    },
     EncoderIsNone,
+    JobFailed(String),
+    PushConstantsUnsupported,
+    InvalidIndirectArgs {
+        step: String,
+        buffer: String,
+        offset: u64,
+        size: u64,
+        usage: BufferUsages,
+    },
+    PushConstantsMismatch {
+        step: String,
+        size: usize,
+        range: Range<u32>,
+    },
+    WorkgroupSizeUnknown(String),
+    BindingsNotReflected(String),
+    UnmatchedBinding {
+        shader: String,
+        name: String,
+    },
+    MissingBinding {
+        shader: String,
+        group: u32,
+        binding: u32,
+        name: Option<String>,
+    },
+    BindingMismatch {
+        pass: String,
+        shader: String,
+        group: u32,
+        binding: u32,
+        name: Option<String>,
+        expected: BindingKind,
+        actual: String,
+    },
+    SwapKindMismatch {
+        step: String,
+        a: String,
+        b: String,
+    },
+    UnexpectedBinding {
+        pass: String,
+        shader: String,
+        group: u32,
+        binding: u32,
+        name: String,
+    },
+    ZeroSizedType(String),
+    RangeOverflow(String),
 }
 
 impl std::error::Error for Error {}
 
+/// A configuration problem found by [`AppComputeWorkerBuilder::try_build`](crate::prelude::AppComputeWorkerBuilder::try_build).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BuildError {
+    /// A buffer, texture, sampler or image was added with a name already in use.
+    DuplicateName(String),
+    /// The initial contents of a buffer couldn't be written.
+    Encoding { name: String, message: String },
+    /// The shader of a pass returned [`ShaderRef::Default`](bevy::render::render_resource::ShaderRef::Default).
+    MissingShader(String),
+    /// A step refers to a resource the worker doesn't have.
+    UnknownResource { step: String, name: String },
+    /// A swap exchanges buffers of different sizes.
+    SwapSizeMismatch {
+        step: String,
+        a: String,
+        b: String,
+        size_a: u64,
+        size_b: u64,
+    },
+    /// A swap exchanges resources of different kinds, eg. a buffer with a texture or an
+    /// image with a texture, or textures with different descriptors.
+    SwapKindMismatch { step: String, a: String, b: String },
+    /// A label was given before adding any step.
+    LabelWithoutStep(String),
+    /// A label was given to two steps.
+    DuplicateLabel(String),
+    /// Push constants were set for a shader, but the device lacks the `PUSH_CONSTANTS` feature.
+    PushConstantsUnsupported(String),
+    /// An indirect pass reads its workgroup counts at an unaligned offset, past the end
+    /// of the buffer, or from a buffer without the `INDIRECT` usage.
+    InvalidIndirectArgs {
+        step: String,
+        buffer: String,
+        offset: u64,
+        size: u64,
+        usage: BufferUsages,
+    },
+    /// Push constants were set before adding any step, or right after a swap.
+    PushConstantsWithoutPass,
+    /// Push constants don't exactly fill the push constant ranges of the shader of a pass.
+    PushConstantsMismatch {
+        step: String,
+        size: usize,
+        range: Range<u32>,
+    },
+    /// A readback option was set for a resource which isn't a staging buffer.
+    NotStaging(String),
+    /// A readback range is empty, out of the buffer, unaligned, or set on a texture.
+    InvalidReadbackRange { name: String, start: u64, end: u64 },
+    /// A staging texture has a depth, stencil or compressed format, whose texels
+    /// can't be read back.
+    UnsupportedStagingFormat { name: String, format: TextureFormat },
+}
+
+impl std::error::Error for BuildError {}
+
+impl std::fmt::Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildError::DuplicateName(name) => write!(f, "Resource {name} was added twice."),
+            BuildError::Encoding { name, message } => {
+                write!(
+                    f,
+                    "Couldn't write the contents of buffer {name}: {message}."
+                )
+            }
+            BuildError::MissingShader(shader) => {
+                write!(f, "{shader} doesn't return a shader from `shader()`.")
+            }
+            BuildError::UnknownResource { step, name } => write!(
+                f,
+                "Step {step} uses `{name}`, but the worker has no resource with that name."
+            ),
+            BuildError::SwapSizeMismatch {
+                step,
+                a,
+                b,
+                size_a,
+                size_b,
+            } => write!(
+                f,
+                "Step {step} swaps `{a}` of {size_a} bytes with `{b}` of {size_b} bytes."
+            ),
+            BuildError::SwapKindMismatch { step, a, b } => write!(
+                f,
+                "Step {step} swaps `{a}` with `{b}`, which aren't both buffers, both images or identical textures."
+            ),
+            BuildError::LabelWithoutStep(label) => {
+                write!(f, "Label `{label}` was given before adding any step.")
+            }
+            BuildError::DuplicateLabel(label) => {
+                write!(f, "Label `{label}` was given to another step.")
+            }
+            BuildError::PushConstantsUnsupported(shader) => write!(
+                f,
+                "Push constants of {shader} require the `PUSH_CONSTANTS` feature, which this device lacks."
+            ),
+            BuildError::InvalidIndirectArgs {
+                step,
+                buffer,
+                offset,
+                size,
+                usage,
+            } => write_invalid_indirect_args(f, step, buffer, *offset, *size, *usage),
+            BuildError::PushConstantsWithoutPass => {
+                write!(f, "Push constants were set before adding any compute pass.")
+            }
+            BuildError::PushConstantsMismatch { step, size, range } => write!(
+                f,
+                "Step {step} sets {size} bytes of push constants, but its shader declares bytes {range:?}."
+            ),
+            BuildError::NotStaging(name) => {
+                write!(f, "`{name}` isn't a staging buffer or staging texture.")
+            }
+            BuildError::InvalidReadbackRange { name, start, end } => write!(
+                f,
+                "Can't read back bytes {start}..{end} of `{name}`: ranges must be non-empty, within a staging buffer and aligned to {COPY_BUFFER_ALIGNMENT} bytes."
+            ),
+            BuildError::UnsupportedStagingFormat { name, format } => write!(
+                f,
+                "Staging texture `{name}` can't be read back with format {format:?}."
+            ),
+        }
+    }
+}
+
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Error::BufferNotFound(name) => write!(f, "Buffer {name} not found."),
+            Error::OutOfBounds {
+                name,
+                start,
+                end,
+                available,
+            } => write!(
+                f,
+                "Bytes {start}..{end} are out of {name}, which holds bytes {}..{}.",
+                available.start, available.end
+            ),
+            Error::UnalignedWrite { name, start, end } => write!(
+                f,
+                "Can't write bytes {start}..{end} of {name}, writes must start and end at multiples of {COPY_BUFFER_ALIGNMENT} bytes."
+            ),
+            Error::RangeLengthMismatch { name, range, data } => write!(
+                f,
+                "Can't write {data} elements to a range of {range} elements of {name}."
+            ),
             Error::StagingBufferNotFound(name) => write!(f, "Staging buffer {name} not found."),
+            Error::StagingBufferNotReady(name) => {
+                write!(f, "Staging buffer {name} has no results to read yet.")
+            }
+            Error::StagingBufferMapFailed { name, message } => {
+                write!(f, "Couldn't map staging buffer {name}: {message}.")
+            }
+            Error::TextureNotFound(name) => write!(f, "Texture {name} not found."),
+            Error::ImageNotReady(name) => write!(f, "Image {name} isn't ready yet."),
             Error::PipelinesEmpty => {
                 write!(f, "Missing pipelines. Have you added your shader plugins?")
             }
             Error::InvalidStep(step) => write!(f, "Invalid step `{step}`."),
+            Error::StepNotFound(step) => write!(f, "Step {step} not found."),
+            Error::DuplicateLabel(label) => write!(f, "Another step is labeled `{label}`."),
+            Error::DuplicateName(name) => write!(
+                f,
+                "The worker already has a buffer, texture, sampler or image called `{name}`."
+            ),
+            Error::Encoding { name, message } => {
+                write!(
+                    f,
+                    "Couldn't write the contents of buffer {name}: {message}."
+                )
+            }
+            Error::ResourceInUse { name, step } => {
+                write!(f, "Can't remove {name}, step {step} still uses it.")
+            }
+            Error::UnknownResource { step, name } => write!(
+                f,
+                "Step {step} uses `{name}`, but the worker has no resource with that name."
+            ),
+            Error::MissingShader(shader) => {
+                write!(f, "{shader} doesn't return a shader from `shader()`.")
+            }
             Error::PipelineNotReady => write!(f, "Pipeline isn't ready yet."),
+            Error::PipelineFailed { shader, message } => {
+                write!(f, "Pipeline of {shader} couldn't be created:\n{message}")
+            }
             Error::EncoderIsNone => write!(f, "The command encoder hasn't been initialized."),
+            Error::JobFailed(error) => {
+                write!(f, "The worker failed while running the job: {error}")
+            }
+            Error::PushConstantsUnsupported => write!(
+                f,
+                "Push constants require the `PUSH_CONSTANTS` feature, which this device lacks."
+            ),
+            Error::InvalidIndirectArgs {
+                step,
+                buffer,
+                offset,
+                size,
+                usage,
+            } => write_invalid_indirect_args(f, step, buffer, *offset, *size, *usage),
+            Error::PushConstantsMismatch { step, size, range } => write!(
+                f,
+                "Step {step} sets {size} bytes of push constants, but its shader declares bytes {range:?}."
+            ),
+            Error::WorkgroupSizeUnknown(shader) => write!(
+                f,
+                "Couldn't read the workgroup size of {shader}, only shaders processed into naga modules are supported."
+            ),
+            Error::BindingsNotReflected(shader) => write!(
+                f,
+                "Couldn't bind the resources of {shader} by name, only shaders processed into naga modules are supported."
+            ),
+            Error::UnmatchedBinding { shader, name } => write!(
+                f,
+                "{shader} uses `{name}`, but the worker has no buffer, texture or sampler with that name."
+            ),
+            Error::MissingBinding {
+                shader,
+                group,
+                binding,
+                name,
+            } => write!(
+                f,
+                "{shader} uses `{}` at @group({group}) @binding({binding}), but no resource was bound there.",
+                name.as_deref().unwrap_or("<unnamed>")
+            ),
+            Error::BindingMismatch {
+                pass,
+                shader,
+                group,
+                binding,
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Pass {pass} binds {actual} at @group({group}) @binding({binding}), but `{}` in {shader} expects {expected}.",
+                name.as_deref().unwrap_or("<unnamed>")
+            ),
+            Error::SwapKindMismatch { step, a, b } => write!(
+                f,
+                "Step {step} swaps images `{a}` and `{b}`, which don't have identical textures."
+            ),
+            Error::UnexpectedBinding {
+                pass,
+                shader,
+                group,
+                binding,
+                name,
+            } => write!(
+                f,
+                "Pass {pass} binds `{name}` at @group({group}) @binding({binding}), but {shader} uses no variable there."
+            ),
+            Error::ZeroSizedType(name) => {
+                write!(f, "Can't access elements of {name} with a zero-sized type.")
+            }
+            Error::RangeOverflow(name) => write!(
+                f,
+                "The byte offsets of the range accessed in {name} overflow."
+            ),
         }
     }
 }
+
+fn write_invalid_indirect_args(
+    f: &mut std::fmt::Formatter<'_>,
+    step: &str,
+    buffer: &str,
+    offset: u64,
+    size: u64,
+    usage: BufferUsages,
+) -> std::fmt::Result {
+    let problem = if !offset.is_multiple_of(4) {
+        "isn't a multiple of 4".to_owned()
+    } else if !usage.contains(BufferUsages::INDIRECT) {
+        "is in a buffer without the `INDIRECT` usage".to_owned()
+    } else {
+        format!("doesn't leave 12 bytes in the {size} bytes of the buffer")
+    };
+
+    write!(
+        f,
+        "Step {step} reads its workgroup counts from `{buffer}` at offset {offset}, which {problem}."
+    )
+}
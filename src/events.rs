@@ -0,0 +1,82 @@
+use std::{marker::PhantomData, time::Duration};
+
+use bevy::prelude::{Event, EventReader};
+
+use crate::{error::Error, traits::ComputeWorker};
+
+/// Sent when running the worker `W` fails.
+///
+/// The worker is then in the [`WorkerState::Failed`](crate::prelude::WorkerState::Failed)
+/// state and stops running until [`AppComputeWorker::execute`](crate::prelude::AppComputeWorker::execute)
+/// is called. Workers built in strict mode panic instead.
+#[derive(Event)]
+pub struct ComputeWorkerError<W: ComputeWorker> {
+    pub error: Error,
+    _phantom: PhantomData<W>,
+}
+
+impl<W: ComputeWorker> ComputeWorkerError<W> {
+    pub(crate) fn new(error: Error) -> Self {
+        Self {
+            error,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// Sent when the worker `W` starts using the recompiled pipeline of a shader,
+/// eg. after the shader was edited with hot reloading enabled.
+///
+/// This is also sent when a shader which failed to compile is fixed, a
+/// [failed](crate::prelude::WorkerState::Failed) worker then runs again.
+#[derive(Event)]
+pub struct ComputePipelineReloaded<W: ComputeWorker> {
+    /// Type path of the reloaded shader.
+    pub shader: String,
+    _phantom: PhantomData<W>,
+}
+
+impl<W: ComputeWorker> ComputePipelineReloaded<W> {
+    pub(crate) fn new(shader: String) -> Self {
+        Self {
+            shader,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// Sent when a run of the worker `W` completes, its staging buffers can then be read.
+#[derive(Event)]
+pub struct ComputeWorkerFinished<W: ComputeWorker> {
+    /// Frame during which the run completed, as counted by [`FrameCount`](bevy::diagnostic::FrameCount).
+    pub frame: u32,
+    /// GPU time of the run.
+    ///
+    /// Sums the timestamps of every pass for workers built with
+    /// [`profile`](crate::prelude::AppComputeWorkerBuilder::profile) on devices supporting
+    /// them, otherwise the time elapsed between the submission of the work to the GPU
+    /// and its completion.
+    pub duration: Duration,
+    _phantom: PhantomData<W>,
+}
+
+impl<W: ComputeWorker> ComputeWorkerFinished<W> {
+    pub(crate) fn new(frame: u32, duration: Duration) -> Self {
+        Self {
+            frame,
+            duration,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// Run condition which is true when a run of the worker `W` completed since the
+/// condition was last evaluated.
+///
+/// ```ignore
+/// app.add_systems(Update, read_results.run_if(on_compute_finished::<MyWorker>()));
+/// ```
+pub fn on_compute_finished<W: ComputeWorker>()
+-> impl FnMut(EventReader<ComputeWorkerFinished<W>>) -> bool + Clone {
+    |mut events: EventReader<ComputeWorkerFinished<W>>| events.read().count() > 0
+}
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use bevy::{
+    asset::{AssetEvent, AssetId, Assets},
+    ecs::{
+        event::EventReader,
+        resource::Resource,
+        system::{Res, ResMut},
+    },
+    image::Image,
+    platform::collections::HashMap,
+    render::{render_asset::RenderAssets, texture::GpuImage},
+};
+use parking_lot::Mutex;
+use wgpu::TextureUsages;
+
+use crate::worker::WorkerTexture;
+
+/// Usages an [`Image`] needs so that workers can bind it as a storage texture.
+pub(crate) const COMPUTE_IMAGE_USAGES: TextureUsages =
+    TextureUsages::STORAGE_BINDING.union(TextureUsages::COPY_SRC);
+
+/// Images bound to compute workers, along with their latest [`GpuImage`] texture.
+///
+/// The same map is shared between the main world, where workers read it, and the
+/// render world, where it is kept in sync with [`RenderAssets<GpuImage>`].
+#[derive(Resource, Clone, Default)]
+pub(crate) struct ComputeImages(Arc<Mutex<HashMap<AssetId<Image>, Option<WorkerTexture>>>>);
+
+impl ComputeImages {
+    /// Start tracking `id`, its texture will be available once the render world prepared it.
+    pub(crate) fn track(&self, id: AssetId<Image>) {
+        self.0.lock().entry(id).or_default();
+    }
+
+    /// Latest texture of the [`GpuImage`] for `id`, if it has been prepared yet.
+    pub(crate) fn get(&self, id: AssetId<Image>) -> Option<WorkerTexture> {
+        self.0.lock().get(&id).cloned().flatten()
+    }
+
+    fn is_tracked(&self, id: AssetId<Image>) -> bool {
+        self.0.lock().contains_key(&id)
+    }
+
+    /// Make sure tracked images are created with [`COMPUTE_IMAGE_USAGES`].
+    ///
+    /// Images loaded from files usually aren't available yet when workers are built.
+    pub(crate) fn add_usages_system(
+        compute_images: Res<ComputeImages>,
+        mut images: ResMut<Assets<Image>>,
+        mut events: EventReader<AssetEvent<Image>>,
+    ) {
+        for event in events.read() {
+            let (AssetEvent::Added { id }
+            | AssetEvent::Modified { id }
+            | AssetEvent::LoadedWithDependencies { id }) = event
+            else {
+                continue;
+            };
+
+            if !compute_images.is_tracked(*id) {
+                continue;
+            }
+
+            let Some(image) = images.get(*id) else {
+                continue;
+            };
+
+            // Only touch the asset when needed, `get_mut` sends a new `Modified` event.
+            if image
+                .texture_descriptor
+                .usage
+                .contains(COMPUTE_IMAGE_USAGES)
+            {
+                continue;
+            }
+
+            if let Some(image) = images.get_mut(*id) {
+                image.texture_descriptor.usage |= COMPUTE_IMAGE_USAGES;
+            }
+        }
+    }
+
+    /// Publish the textures of freshly prepared [`GpuImage`]s to the workers.
+    pub(crate) fn prepare_system(
+        compute_images: Res<ComputeImages>,
+        gpu_images: Res<RenderAssets<GpuImage>>,
+    ) {
+        let mut compute_images = compute_images.0.lock();
+        for (id, texture) in compute_images.iter_mut() {
+            let Some(gpu_image) = gpu_images.get(*id) else {
+                continue;
+            };
+
+            if !gpu_image
+                .texture
+                .usage()
+                .contains(TextureUsages::STORAGE_BINDING)
+            {
+                continue;
+            }
+
+            if texture
+                .as_ref()
+                .is_some_and(|texture| texture.texture.id() == gpu_image.texture.id())
+            {
+                continue;
+            }
+
+            *texture = Some(WorkerTexture {
+                texture: gpu_image.texture.clone(),
+                view: gpu_image.texture_view.clone(),
+            });
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, Waker},
+};
+
+use bevy::platform::collections::HashMap;
+use bytemuck::{NoUninit, Pod, bytes_of, cast_slice, cast_slice_mut, pod_read_unaligned};
+use parking_lot::Mutex;
+
+use crate::error::{Error, Result};
+
+/// Inputs of a run requested with [`AppComputeWorker::submit_job`](crate::prelude::AppComputeWorker::submit_job).
+///
+/// The writes are applied to the buffers of the worker right before the job runs,
+/// so that queued jobs don't overwrite each other's inputs. They aren't undone
+/// afterwards: buffers the job doesn't write keep whatever previous runs left in them.
+#[derive(Clone, Debug, Default)]
+pub struct ComputeJob {
+    pub(crate) writes: Vec<(String, Vec<u8>)>,
+}
+
+impl ComputeJob {
+    /// Create a job without inputs, running the worker with its buffers as they are.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write `data` to the buffer `target` before running the job.
+    pub fn write<T: NoUninit>(mut self, target: &str, data: &T) -> Self {
+        self.writes
+            .push((target.to_owned(), bytes_of(data).to_vec()));
+        self
+    }
+
+    /// Write `data` to the buffer `target` before running the job.
+    pub fn write_slice<T: NoUninit>(mut self, target: &str, data: &[T]) -> Self {
+        self.writes
+            .push((target.to_owned(), cast_slice(data).to_vec()));
+        self
+    }
+}
+
+/// Contents of the staging buffers of a worker, read back once a job ran.
+#[derive(Clone, Debug, Default)]
+pub struct ComputeJobOutput {
+    pub(crate) staging_buffers: HashMap<String, Vec<u8>>,
+}
+
+impl ComputeJobOutput {
+    /// Read data from `target` staging buffer, return raw bytes.
+    ///
+    /// Texture staging buffers are returned without their row padding.
+    pub fn try_read_raw(&self, target: &str) -> Result<&[u8]> {
+        self.staging_buffers
+            .get(target)
+            .map(Vec::as_slice)
+            .ok_or_else(|| Error::StagingBufferNotFound(target.to_owned()))
+    }
+
+    /// Try Read data from `target` staging buffer, return a single `B: Pod`
+    pub fn try_read<B: Pod>(&self, target: &str) -> Result<B> {
+        let bytes = self.try_read_raw(target)?;
+        let Some(bytes) = bytes.get(..size_of::<B>()) else {
+            return Err(Error::OutOfBounds {
+                name: target.to_owned(),
+                start: 0,
+                end: size_of::<B>() as u64,
+                available: 0..bytes.len() as u64,
+            });
+        };
+        Ok(pod_read_unaligned(bytes))
+    }
+
+    /// Try Read data from `target` staging buffer, return a single `B: Pod`
+    /// In case of error, this function will panic.
+    pub fn read<B: Pod>(&self, target: &str) -> B {
+        self.try_read(target).unwrap()
+    }
+
+    /// Try Read data from `target` staging buffer, return a vector of `B: Pod`
+    pub fn try_read_vec<B: Pod>(&self, target: &str) -> Result<Vec<B>> {
+        let bytes = self.try_read_raw(target)?;
+        let len = bytes.len() / size_of::<B>();
+        let mut result = vec![B::zeroed(); len];
+        cast_slice_mut::<B, u8>(&mut result).copy_from_slice(&bytes[..len * size_of::<B>()]);
+        Ok(result)
+    }
+
+    /// Try Read data from `target` staging buffer, return a vector of `B: Pod`
+    /// In case of error, this function will panic.
+    pub fn read_vec<B: Pod>(&self, target: &str) -> Vec<B> {
+        self.try_read_vec(target).unwrap()
+    }
+}
+
+#[derive(Default)]
+struct JobSlot {
+    result: Option<Result<ComputeJobOutput>>,
+    finished: bool,
+    waker: Option<Waker>,
+}
+
+/// Handle to a job submitted to a worker, resolving to its [`ComputeJobOutput`].
+///
+/// It can be awaited, eg. from a task spawned on Bevy's `AsyncComputeTaskPool`, or
+/// polled from a system with [`Self::take_result`]. Jobs fail with the error which
+/// stopped the worker, if their inputs couldn't be written, or if the worker is
+/// dropped before they run.
+pub struct ComputeJobHandle {
+    slot: Arc<Mutex<JobSlot>>,
+}
+
+impl ComputeJobHandle {
+    /// Whether the job ran, or failed.
+    pub fn is_finished(&self) -> bool {
+        self.slot.lock().finished
+    }
+
+    /// Take the result of the job if it's finished.
+    ///
+    /// The result can only be taken once, either by this method or by awaiting the handle.
+    pub fn take_result(&mut self) -> Option<Result<ComputeJobOutput>> {
+        self.slot.lock().result.take()
+    }
+}
+
+impl Future for ComputeJobHandle {
+    type Output = Result<ComputeJobOutput>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.slot.lock();
+        match slot.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// A job waiting to run, or running, on a worker.
+pub(crate) struct PendingJob {
+    pub(crate) job: ComputeJob,
+    slot: Arc<Mutex<JobSlot>>,
+}
+
+impl PendingJob {
+    pub(crate) fn new(job: ComputeJob) -> (Self, ComputeJobHandle) {
+        let slot = Arc::new(Mutex::new(JobSlot::default()));
+        let handle = ComputeJobHandle { slot: slot.clone() };
+        (Self { job, slot }, handle)
+    }
+
+    /// Hand `result` to the [`ComputeJobHandle`] of the job.
+    pub(crate) fn resolve(self, result: Result<ComputeJobOutput>) {
+        self.finish(result);
+    }
+
+    fn finish(&self, result: Result<ComputeJobOutput>) {
+        let mut slot = self.slot.lock();
+        slot.result = Some(result);
+        slot.finished = true;
+        if let Some(waker) = slot.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Jobs dropped with their worker before running fail, instead of never resolving.
+impl Drop for PendingJob {
+    fn drop(&mut self) {
+        if !self.slot.lock().finished {
+            self.finish(Err(Error::JobFailed(
+                "The worker was dropped before the job ran.".to_owned(),
+            )));
+        }
+    }
+}
@@ -10,9 +10,16 @@ use bevy::{
 };
 use pipeline_cache::PipelineCache;
 
-mod error;
+mod bind_groups;
+mod diagnostics;
+pub mod error;
+mod events;
+mod images;
+mod jobs;
 mod pipeline_cache;
 mod plugin;
+mod profiling;
+mod reflection;
 mod traits;
 mod worker;
 mod worker_builder;
@@ -20,12 +27,22 @@ mod worker_builder;
 /// Helper module to import most used elements.
 pub mod prelude {
     pub use crate::{
+        diagnostics::ComputeWorkerDiagnostics,
+        error::BuildError,
+        events::{
+            ComputePipelineReloaded, ComputeWorkerError, ComputeWorkerFinished, on_compute_finished,
+        },
+        jobs::{ComputeJob, ComputeJobHandle, ComputeJobOutput},
         plugin::{
             AppComputePlugin, AppComputeWorkerPlugin, BevyEasyComputePostUpdateSet,
             BevyEasyComputeSet,
         },
+        profiling::RunTimings,
+        reflection::BindingKind,
         traits::{ComputeShader, ComputeWorker},
-        worker::AppComputeWorker,
+        worker::{
+            AppComputeWorker, BufferKind, PipelineState, ReadbackPolicy, StepId, WorkerState,
+        },
         worker_builder::AppComputeWorkerBuilder,
     };
 
@@ -2,6 +2,7 @@
 
 // Needing this since bevy does'nt expose set_shader and remove_shader
 
+use crate::reflection::{ComputeEntryPoint, ShaderBinding, compute_entry_points};
 use bevy::asset::{AssetEvent, AssetId, Assets};
 use bevy::ecs::{
     event::EventReader,
@@ -65,8 +66,13 @@ impl AppCachedComputePipelineId {
     }
 }
 
+/// Values of pipeline-overridable constants by name, as wgpu expects them.
+pub type Constants = std::collections::HashMap<String, f64>;
+
 pub struct CachedPipeline {
     pub descriptor: PipelineDescriptor,
+    /// Values of the pipeline-overridable constants, only used by compute pipelines.
+    pub constants: Constants,
     pub state: CachedPipelineState,
 }
 
@@ -112,6 +118,8 @@ impl CachedPipelineState {
 struct ShaderData {
     pipelines: HashSet<CachedPipelineId>,
     processed_shaders: HashMap<Box<[ShaderDefVal]>, Arc<WgpuWrapper<ShaderModule>>>,
+    /// Compute entry points of the processed shaders compiled from naga modules.
+    entry_points: HashMap<Box<[ShaderDefVal]>, Arc<[ComputeEntryPoint]>>,
     resolved_imports: HashMap<ShaderImport, AssetId<Shader>>,
     dependents: HashSet<AssetId<Shader>>,
 }
@@ -210,6 +218,8 @@ impl ShaderCache {
         let module = match data.processed_shaders.entry_ref(shader_defs) {
             EntryRef::Occupied(entry) => entry.into_mut(),
             EntryRef::Vacant(entry) => {
+                let key: Box<[ShaderDefVal]> = shader_defs.into();
+                let mut entry_points = None;
                 let mut shader_defs = shader_defs.to_vec();
                 #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
                 {
@@ -267,6 +277,7 @@ impl ShaderCache {
                             .unwrap();
 
                             let naga = naga::front::wgsl::parse_str(&compiled.to_string()).unwrap();
+                            entry_points = Some(compute_entry_points(&naga));
                             ShaderSource::Naga(Cow::Owned(naga))
                         } else {
                             panic!("Wesl shaders must be imported from a file");
@@ -310,6 +321,7 @@ impl ShaderCache {
                                 ..shader.into()
                             },
                         )?;
+                        entry_points = Some(compute_entry_points(&naga));
 
                         #[cfg(not(feature = "decoupled_naga"))]
                         {
@@ -369,6 +381,10 @@ impl ShaderCache {
                     return Err(PipelineCacheError::CreateShaderModule(description));
                 }
 
+                if let Some(entry_points) = entry_points {
+                    data.entry_points.insert(key, entry_points.into());
+                }
+
                 entry.insert(Arc::new(WgpuWrapper::new(shader_module)))
             }
         };
@@ -382,6 +398,7 @@ impl ShaderCache {
         while let Some(handle) = shaders_to_clear.pop() {
             if let Some(data) = self.data.get_mut(&handle) {
                 data.processed_shaders.clear();
+                data.entry_points.clear();
                 pipelines_to_queue.extend(data.pipelines.iter().copied());
                 shaders_to_clear.extend(data.dependents.iter().copied());
 
@@ -439,6 +456,15 @@ impl ShaderCache {
         pipelines_to_queue
     }
 
+    /// Compute entry points of a processed shader, if it was compiled from a naga module.
+    fn entry_points(
+        &self,
+        id: AssetId<Shader>,
+        shader_defs: &[ShaderDefVal],
+    ) -> Option<Arc<[ComputeEntryPoint]>> {
+        self.data.get(&id)?.entry_points.get(shader_defs).cloned()
+    }
+
     fn remove(&mut self, id: AssetId<Shader>) -> Vec<CachedPipelineId> {
         let pipelines_to_queue = self.clear(id);
         if let Some(shader) = self.shaders.remove(&id) {
@@ -577,11 +603,103 @@ impl PipelineCache {
         }
     }
 
+    /// Get the state of a cached compute pipeline.
+    ///
+    /// See [`PipelineCache::queue_compute_pipeline()`].
+    #[inline]
+    pub fn get_compute_pipeline_state(
+        &self,
+        id: AppCachedComputePipelineId,
+    ) -> &CachedPipelineState {
+        // If the pipeline id isn't in `pipelines`, it's queued in `new_pipelines`
+        self.pipelines
+            .get(id.0)
+            .map_or(&CachedPipelineState::Queued, |pipeline| &pipeline.state)
+    }
+
+    /// Description of the error which prevented a compute pipeline from being created.
+    ///
+    /// # Returns
+    ///
+    /// This method returns `None` if the pipeline was created or is still being created,
+    /// including when its creation is retried because its shader isn't loaded yet.
+    pub fn get_compute_pipeline_error(&self, id: AppCachedComputePipelineId) -> Option<String> {
+        match self.get_compute_pipeline_state(id) {
+            CachedPipelineState::Err(PipelineCacheError::ProcessShaderError(err)) => {
+                Some(err.emit_to_string(&self.shader_cache.lock().unwrap().composer))
+            }
+            CachedPipelineState::Err(PipelineCacheError::CreateShaderModule(description)) => {
+                Some(description.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Workgroup size of the entry point of a compute pipeline, read from its shader.
+    ///
+    /// Sizes set by pipeline-overridable constants are resolved with the constants of the
+    /// pipeline, falling back to the defaults declared in the shader.
+    ///
+    /// # Returns
+    ///
+    /// This method returns `None` if the shader hasn't been processed yet, if it wasn't processed
+    /// into a naga module (eg. SPIR-V shaders), or if an override can't be evaluated.
+    pub fn get_compute_pipeline_workgroup_size(
+        &self,
+        id: AppCachedComputePipelineId,
+    ) -> Option<[u32; 3]> {
+        let (cached_pipeline, entry_point) = self.compute_pipeline_entry_point(id)?;
+
+        let mut workgroup_size = [0; 3];
+        for (size, axis) in workgroup_size.iter_mut().zip(&entry_point.workgroup_size) {
+            *size = axis.as_ref()?.evaluate(&cached_pipeline.constants)?;
+        }
+
+        Some(workgroup_size)
+    }
+
+    /// Resources used by the entry point of a compute pipeline, read from its shader.
+    ///
+    /// Returns `None` if the shader hasn't been processed yet, or if it wasn't processed
+    /// into a naga module (eg. SPIR-V shaders).
+    pub(crate) fn get_compute_pipeline_bindings(
+        &self,
+        id: AppCachedComputePipelineId,
+    ) -> Option<Vec<ShaderBinding>> {
+        let (_, entry_point) = self.compute_pipeline_entry_point(id)?;
+        Some(entry_point.bindings)
+    }
+
+    /// A compute pipeline and its entry point, read from its shader.
+    fn compute_pipeline_entry_point(
+        &self,
+        id: AppCachedComputePipelineId,
+    ) -> Option<(&CachedPipeline, ComputeEntryPoint)> {
+        let cached_pipeline = self.pipelines.get(id.0)?;
+        let PipelineDescriptor::ComputePipelineDescriptor(descriptor) = &cached_pipeline.descriptor
+        else {
+            return None;
+        };
+
+        let entry_points = self
+            .shader_cache
+            .lock()
+            .unwrap()
+            .entry_points(descriptor.shader.id(), &descriptor.shader_defs)?;
+        let entry_point = entry_points
+            .iter()
+            .find(|entry_point| entry_point.name == descriptor.entry_point)?;
+
+        Some((cached_pipeline, entry_point.clone()))
+    }
+
     /// Insert a compute pipeline into the cache, and queue its creation.
     ///
     /// The pipeline is always inserted and queued for creation. There is no attempt to deduplicate it with
     /// an already cached pipeline.
     ///
+    /// `constants` are the values of the pipeline-overridable constants declared in the shader.
+    ///
     /// # Returns
     ///
     /// This method returns the unique compute shader ID of the cached pipeline, which can be used to query
@@ -593,6 +711,7 @@ impl PipelineCache {
     pub fn queue_compute_pipeline(
         &self,
         descriptor: ComputePipelineDescriptor,
+        constants: Constants,
     ) -> AppCachedComputePipelineId {
         let mut new_pipelines = self
             .new_pipelines
@@ -601,6 +720,7 @@ impl PipelineCache {
         let id = AppCachedComputePipelineId(self.pipelines.len() + new_pipelines.len());
         new_pipelines.push(CachedPipeline {
             descriptor: PipelineDescriptor::ComputePipelineDescriptor(Box::new(descriptor)),
+            constants,
             state: CachedPipelineState::Queued,
         });
         id
@@ -608,6 +728,16 @@ impl PipelineCache {
 
     pub(crate) fn set_shader(&mut self, id: AssetId<Shader>, shader: &Shader) {
         let mut shader_cache = self.shader_cache.lock().unwrap();
+        // Shaders are reported both when they're added and once their dependencies are
+        // loaded, don't recompile their pipelines if nothing changed in between.
+        if shader_cache
+            .shaders
+            .get(&id)
+            .is_some_and(|current| same_shader(current, shader))
+        {
+            return;
+        }
+
         let pipelines_to_queue = shader_cache.set_shader(id, shader.clone());
         for cached_pipeline in pipelines_to_queue {
             self.pipelines[cached_pipeline].state = CachedPipelineState::Queued;
@@ -739,6 +869,7 @@ impl PipelineCache {
         &mut self,
         id: CachedPipelineId,
         descriptor: ComputePipelineDescriptor,
+        constants: Constants,
     ) -> CachedPipelineState {
         let device = self.device.clone();
         let shader_cache = self.shader_cache.clone();
@@ -777,9 +908,8 @@ impl PipelineCache {
                     layout: layout.as_ref().map(|layout| -> &PipelineLayout { layout }),
                     module: &compute_module,
                     entry_point: Some(&descriptor.entry_point),
-                    // TODO: Expose the rest of this somehow
                     compilation_options: PipelineCompilationOptions {
-                        constants: &default(),
+                        constants: &constants,
                         zero_initialize_workgroup_memory: descriptor
                             .zero_initialize_workgroup_memory,
                     },
@@ -830,9 +960,12 @@ impl PipelineCache {
                     PipelineDescriptor::RenderPipelineDescriptor(descriptor) => {
                         self.start_create_render_pipeline(id, *descriptor.clone())
                     }
-                    PipelineDescriptor::ComputePipelineDescriptor(descriptor) => {
-                        self.start_create_compute_pipeline(id, *descriptor.clone())
-                    }
+                    PipelineDescriptor::ComputePipelineDescriptor(descriptor) => self
+                        .start_create_compute_pipeline(
+                            id,
+                            *descriptor.clone(),
+                            cached_pipeline.constants.clone(),
+                        ),
                 };
             }
 
@@ -926,6 +1059,22 @@ pub enum PipelineCacheError {
     CreateShaderModule(String),
 }
 
+/// Whether `a` and `b` would be processed into the same shader module.
+fn same_shader(a: &Shader, b: &Shader) -> bool {
+    let same_source = match (&a.source, &b.source) {
+        (Source::Wgsl(a), Source::Wgsl(b)) => a == b,
+        (Source::Glsl(a, stage_a), Source::Glsl(b, stage_b)) => a == b && stage_a == stage_b,
+        (Source::SpirV(a), Source::SpirV(b)) => a == b,
+        _ => false,
+    };
+
+    same_source
+        && a.path == b.path
+        && a.import_path == b.import_path
+        && a.imports == b.imports
+        && a.shader_defs == b.shader_defs
+}
+
 // TODO: This needs to be kept up to date with the capabilities in the `create_validator` function in wgpu-core
 // https://github.com/gfx-rs/wgpu/blob/trunk/wgpu-core/src/device/mod.rs#L449
 // We can't use the `wgpu-core` function to detect the device's capabilities because `wgpu-core` isn't included in WebGPU builds.
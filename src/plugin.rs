@@ -1,12 +1,24 @@
 use std::marker::PhantomData;
 
 use bevy::{
+    asset::AssetEvents,
     prelude::*,
-    render::renderer::{RenderAdapter, RenderDevice},
+    render::{
+        Render, RenderApp, RenderSet,
+        render_asset::prepare_assets,
+        renderer::{RenderAdapter, RenderDevice},
+        texture::GpuImage,
+    },
 };
 
 use crate::{
-    extract_shaders, pipeline_cache::PipelineCache, traits::ComputeWorker, worker::AppComputeWorker,
+    diagnostics::ComputeWorkerDiagnostics,
+    events::{ComputePipelineReloaded, ComputeWorkerError, ComputeWorkerFinished},
+    extract_shaders,
+    images::ComputeImages,
+    pipeline_cache::PipelineCache,
+    traits::ComputeWorker,
+    worker::AppComputeWorker,
 };
 
 /// The main plugin. Always include it if you want to use `bevy_app_compute`
@@ -22,13 +34,31 @@ impl Plugin for AppComputePlugin {
         app.configure_sets(Update, BevyEasyComputeSet::ExtractPipelines)
             .configure_sets(PostUpdate, BevyEasyComputePostUpdateSet::ExecuteCompute);
 
+        let compute_images = ComputeImages::default();
+
         app.insert_resource(PipelineCache::new(render_device, render_adapter, true))
+            .insert_resource(compute_images.clone())
             .add_systems(PreUpdate, extract_shaders)
+            // Images only used by the render world are removed from the main world as soon
+            // as they're extracted, their usages must be fixed during the frame they're added.
+            .add_systems(
+                PostUpdate,
+                ComputeImages::add_usages_system.after(AssetEvents),
+            )
             .add_systems(
                 Update,
                 PipelineCache::process_pipeline_queue_system
                     .in_set(BevyEasyComputeSet::ExtractPipelines),
             );
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.insert_resource(compute_images).add_systems(
+                Render,
+                ComputeImages::prepare_system
+                    .in_set(RenderSet::PrepareAssets)
+                    .after(prepare_assets::<GpuImage>),
+            );
+        }
     }
 }
 
@@ -68,6 +98,9 @@ impl<W: ComputeWorker> Plugin for AppComputeWorkerPlugin<W> {
         let worker = W::build(app.world_mut());
 
         app.insert_resource(worker)
+            .add_event::<ComputeWorkerError<W>>()
+            .add_event::<ComputePipelineReloaded<W>>()
+            .add_event::<ComputeWorkerFinished<W>>()
             .add_systems(
                 Update,
                 AppComputeWorker::<W>::extract_pipelines
@@ -76,9 +109,15 @@ impl<W: ComputeWorker> Plugin for AppComputeWorkerPlugin<W> {
             )
             .add_systems(
                 PostUpdate,
-                (AppComputeWorker::<W>::unmap_all, AppComputeWorker::<W>::run)
+                (
+                    AppComputeWorker::<W>::update_workgroups,
+                    AppComputeWorker::<W>::run,
+                    ComputeWorkerDiagnostics::<W>::measure,
+                )
                     .in_set(BevyEasyComputePostUpdateSet::ExecuteCompute)
                     .chain(),
             );
+
+        ComputeWorkerDiagnostics::<W>::register(app);
     }
 }
@@ -0,0 +1,195 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use bevy::render::{
+    render_resource::{Buffer, BufferDescriptor},
+    renderer::{RenderDevice, RenderQueue},
+};
+use bytemuck::cast_slice;
+use wgpu::{
+    BufferUsages, CommandEncoder, ComputePassTimestampWrites, QUERY_SIZE, QuerySet,
+    QuerySetDescriptor, QueryType,
+};
+
+use crate::worker::StepId;
+
+/// Timings of the most recently finished run of a worker built with
+/// [`AppComputeWorkerBuilder::profile`](crate::prelude::AppComputeWorkerBuilder::profile).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RunTimings {
+    /// GPU duration of each compute pass of the run, in order.
+    ///
+    /// Empty when the device lacks [`wgpu::Features::TIMESTAMP_QUERY`].
+    pub passes: Vec<(StepId, Duration)>,
+    /// Time between submitting the run and the GPU completing it, measured on the CPU.
+    pub total: Duration,
+}
+
+/// Timestamps written at the beginning and end of every compute pass of a run.
+pub(crate) struct TimestampQueries {
+    query_set: QuerySet,
+    /// How many passes the query set has room for.
+    capacity: u32,
+    resolve_buffer: Buffer,
+    /// One buffer per frame in flight the timestamps are copied to, to be mapped.
+    frames: Vec<Buffer>,
+    /// Passes recorded in the current run, in the order of their queries.
+    passes: Vec<StepId>,
+    /// Nanoseconds per timestamp tick.
+    period: f32,
+}
+
+impl TimestampQueries {
+    pub(crate) fn new(
+        render_device: &RenderDevice,
+        render_queue: &RenderQueue,
+        capacity: u32,
+        frames: usize,
+    ) -> Self {
+        let capacity = capacity.max(1);
+        let size = u64::from(capacity) * 2 * QUERY_SIZE as u64;
+
+        Self {
+            query_set: render_device
+                .wgpu_device()
+                .create_query_set(&QuerySetDescriptor {
+                    label: Some("timestamps"),
+                    ty: QueryType::Timestamp,
+                    count: capacity * 2,
+                }),
+            capacity,
+            resolve_buffer: render_device.create_buffer(&BufferDescriptor {
+                label: Some("timestamps"),
+                size,
+                usage: BufferUsages::QUERY_RESOLVE | BufferUsages::COPY_SRC,
+                mapped_at_creation: false,
+            }),
+            frames: (0..frames)
+                .map(|_| {
+                    render_device.create_buffer(&BufferDescriptor {
+                        label: Some("timestamps"),
+                        size,
+                        usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+                        mapped_at_creation: false,
+                    })
+                })
+                .collect(),
+            passes: vec![],
+            period: render_queue.get_timestamp_period(),
+        }
+    }
+
+    /// Make room for `passes` passes, before recording a run.
+    ///
+    /// Runs in flight keep the previous buffers alive until they finish.
+    pub(crate) fn reserve(
+        &mut self,
+        render_device: &RenderDevice,
+        render_queue: &RenderQueue,
+        passes: usize,
+    ) {
+        self.passes.clear();
+        if passes > self.capacity as usize {
+            *self = Self::new(
+                render_device,
+                render_queue,
+                passes as u32,
+                self.frames.len(),
+            );
+        }
+    }
+
+    /// Queries to write the timestamps of the pass `step` to.
+    pub(crate) fn begin_pass(&mut self, step: StepId) -> ComputePassTimestampWrites<'_> {
+        let index = self.passes.len() as u32;
+        self.passes.push(step);
+
+        ComputePassTimestampWrites {
+            query_set: &self.query_set,
+            beginning_of_pass_write_index: Some(index * 2),
+            end_of_pass_write_index: Some(index * 2 + 1),
+        }
+    }
+
+    /// Copy the timestamps of the recorded passes to the buffer of `frame`.
+    pub(crate) fn resolve(&self, encoder: &mut CommandEncoder, frame: usize) {
+        if self.passes.is_empty() {
+            return;
+        }
+
+        let count = self.passes.len() as u32 * 2;
+        encoder.resolve_query_set(&self.query_set, 0..count, &self.resolve_buffer, 0);
+        encoder.copy_buffer_to_buffer(
+            &self.resolve_buffer,
+            0,
+            &self.frames[frame],
+            0,
+            u64::from(count) * QUERY_SIZE as u64,
+        );
+    }
+
+    /// Map the timestamps of the submitted run once it completes, counting down `pending`.
+    pub(crate) fn map(&mut self, frame: usize, pending: &Arc<AtomicUsize>) -> PendingTimestamps {
+        let passes = std::mem::take(&mut self.passes);
+        let buffer = self.frames[frame].clone();
+        let mapped = Arc::new(AtomicBool::new(false));
+
+        if passes.is_empty() {
+            pending.fetch_sub(1, Ordering::AcqRel);
+        } else {
+            let size = passes.len() as u64 * 2 * QUERY_SIZE as u64;
+            let done = mapped.clone();
+            let pending = pending.clone();
+            buffer
+                .slice(..size)
+                .map_async(wgpu::MapMode::Read, move |result| {
+                    done.store(result.is_ok(), Ordering::Release);
+                    pending.fetch_sub(1, Ordering::AcqRel);
+                });
+        }
+
+        PendingTimestamps {
+            buffer,
+            passes,
+            mapped,
+            period: self.period,
+        }
+    }
+}
+
+/// Timestamps of a submitted run, readable once it finished.
+pub(crate) struct PendingTimestamps {
+    buffer: Buffer,
+    passes: Vec<StepId>,
+    mapped: Arc<AtomicBool>,
+    period: f32,
+}
+
+impl PendingTimestamps {
+    /// Duration of each pass, unmapping the buffer for the next run of the frame.
+    pub(crate) fn read(self) -> Vec<(StepId, Duration)> {
+        if !self.mapped.load(Ordering::Acquire) {
+            return vec![];
+        }
+
+        let size = self.passes.len() as u64 * 2 * QUERY_SIZE as u64;
+        let timestamps: Vec<u64> =
+            cast_slice(&self.buffer.slice(..size).get_mapped_range()).to_vec();
+        self.buffer.unmap();
+
+        self.passes
+            .into_iter()
+            .zip(timestamps.chunks_exact(2))
+            .map(|(step, timestamps)| {
+                let ticks = timestamps[1].saturating_sub(timestamps[0]);
+                let nanos = ticks as f64 * f64::from(self.period);
+                (step, Duration::from_nanos(nanos as u64))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,247 @@
+use std::{collections::HashSet, fmt};
+
+use naga::{
+    AddressSpace, Block, Expression, Function, GlobalVariable, Handle, ImageClass, Literal, Module,
+    ShaderStage, Statement, StorageAccess, TypeInner,
+};
+
+use crate::pipeline_cache::Constants;
+
+/// A resource a shader entry point expects to be bound.
+#[derive(Clone, Debug)]
+pub(crate) struct ShaderBinding {
+    pub(crate) group: u32,
+    pub(crate) binding: u32,
+    /// Name of the global variable in the shader source.
+    pub(crate) name: Option<String>,
+    pub(crate) kind: BindingKind,
+}
+
+/// What kind of resource a shader expects at a binding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BindingKind {
+    /// A uniform buffer of at least `min_size` bytes.
+    Uniform {
+        min_size: u64,
+    },
+    /// A storage buffer of at least `min_size` bytes, runtime-sized arrays counting
+    /// for one element.
+    Storage {
+        read_only: bool,
+        min_size: u64,
+    },
+    Texture,
+    StorageTexture,
+    Sampler,
+    /// Resources which aren't validated, eg. binding arrays.
+    Other,
+}
+
+impl BindingKind {
+    fn of(module: &Module, variable: &GlobalVariable) -> Self {
+        let inner = &module.types[variable.ty].inner;
+        let min_size = inner.size(module.to_ctx()) as u64;
+
+        match (variable.space, inner) {
+            (AddressSpace::Uniform, _) => BindingKind::Uniform { min_size },
+            (AddressSpace::Storage { access }, _) => BindingKind::Storage {
+                read_only: !access.contains(StorageAccess::STORE),
+                min_size,
+            },
+            (
+                AddressSpace::Handle,
+                TypeInner::Image {
+                    class: ImageClass::Storage { .. },
+                    ..
+                },
+            ) => BindingKind::StorageTexture,
+            (AddressSpace::Handle, TypeInner::Image { .. }) => BindingKind::Texture,
+            (AddressSpace::Handle, TypeInner::Sampler { .. }) => BindingKind::Sampler,
+            _ => BindingKind::Other,
+        }
+    }
+}
+
+impl fmt::Display for BindingKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BindingKind::Uniform { min_size } => {
+                write!(f, "a uniform buffer of at least {min_size} bytes")
+            }
+            BindingKind::Storage {
+                read_only: true,
+                min_size,
+            } => write!(f, "a storage buffer of at least {min_size} bytes"),
+            BindingKind::Storage {
+                read_only: false,
+                min_size,
+            } => write!(
+                f,
+                "a read/write storage buffer of at least {min_size} bytes"
+            ),
+            BindingKind::Texture => write!(f, "a texture"),
+            BindingKind::StorageTexture => write!(f, "a storage texture"),
+            BindingKind::Sampler => write!(f, "a sampler"),
+            BindingKind::Other => write!(f, "a resource"),
+        }
+    }
+}
+
+/// Resources used by the entry point `function`, sorted by group and binding.
+///
+/// Globals declared by the module but only used by other entry points are left out, as
+/// pipeline layouts don't include them.
+fn entry_point_bindings(module: &Module, function: &Function) -> Vec<ShaderBinding> {
+    let mut used = HashSet::new();
+    used_globals(module, function, &mut used);
+
+    let mut bindings = module
+        .global_variables
+        .iter()
+        .filter(|(handle, _)| used.contains(handle))
+        .filter_map(|(_, variable)| {
+            let binding = variable.binding.as_ref()?;
+            Some(ShaderBinding {
+                group: binding.group,
+                binding: binding.binding,
+                name: variable.name.clone(),
+                kind: BindingKind::of(module, variable),
+            })
+        })
+        .collect::<Vec<_>>();
+    bindings.sort_by_key(|binding| (binding.group, binding.binding));
+
+    bindings
+}
+
+/// Collect the globals used by `function`, directly or through the functions it calls.
+fn used_globals(module: &Module, function: &Function, used: &mut HashSet<Handle<GlobalVariable>>) {
+    used.extend(
+        function
+            .expressions
+            .iter()
+            .filter_map(|(_, expression)| match expression {
+                Expression::GlobalVariable(handle) => Some(*handle),
+                _ => None,
+            }),
+    );
+
+    let mut calls = vec![];
+    called_functions(&function.body, &mut calls);
+    for call in calls {
+        used_globals(module, &module.functions[call], used);
+    }
+}
+
+/// Collect the functions called by the statements of `block`.
+fn called_functions(block: &Block, calls: &mut Vec<Handle<Function>>) {
+    for statement in block.iter() {
+        match statement {
+            Statement::Call { function, .. } => calls.push(*function),
+            Statement::Block(block) => called_functions(block, calls),
+            Statement::If { accept, reject, .. } => {
+                called_functions(accept, calls);
+                called_functions(reject, calls);
+            }
+            Statement::Switch { cases, .. } => {
+                for case in cases {
+                    called_functions(&case.body, calls);
+                }
+            }
+            Statement::Loop {
+                body, continuing, ..
+            } => {
+                called_functions(body, calls);
+                called_functions(continuing, calls);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Workgroup size of a compute entry point along one axis.
+#[derive(Clone, Debug)]
+pub(crate) enum WorkgroupSize {
+    Fixed(u32),
+    /// Set by a pipeline-overridable constant, falling back to its initializer.
+    Override {
+        id: Option<u16>,
+        name: Option<String>,
+        default: Option<Box<WorkgroupSize>>,
+    },
+}
+
+impl WorkgroupSize {
+    /// Read a global expression of `module` made of literals, constants and overrides.
+    fn of(module: &Module, expression: Handle<Expression>) -> Option<Self> {
+        match module.global_expressions[expression] {
+            Expression::Literal(literal) => match literal {
+                Literal::U32(value) => Some(value),
+                Literal::I32(value) => u32::try_from(value).ok(),
+                Literal::AbstractInt(value) => u32::try_from(value).ok(),
+                _ => None,
+            }
+            .map(WorkgroupSize::Fixed),
+            Expression::Constant(constant) => Self::of(module, module.constants[constant].init),
+            Expression::Override(handle) => {
+                let override_ = &module.overrides[handle];
+                Some(WorkgroupSize::Override {
+                    id: override_.id,
+                    name: override_.name.clone(),
+                    default: override_
+                        .init
+                        .and_then(|init| Self::of(module, init))
+                        .map(Box::new),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Resolve the size with the pipeline-overridable constants of a pipeline.
+    pub(crate) fn evaluate(&self, constants: &Constants) -> Option<u32> {
+        match self {
+            WorkgroupSize::Fixed(size) => Some(*size),
+            WorkgroupSize::Override { id, name, default } => {
+                let value = id
+                    .and_then(|id| constants.get(&id.to_string()))
+                    .or_else(|| constants.get(name.as_ref()?));
+
+                match value {
+                    Some(value) => Some(*value as u32),
+                    None => default.as_ref()?.evaluate(constants),
+                }
+            }
+        }
+    }
+}
+
+/// A compute entry point of a processed shader.
+#[derive(Clone, Debug)]
+pub(crate) struct ComputeEntryPoint {
+    pub(crate) name: String,
+    /// `None` along the axes set by expressions which can't be evaluated ahead of time.
+    pub(crate) workgroup_size: [Option<WorkgroupSize>; 3],
+    /// Resources used by the entry point, sorted by group and binding.
+    pub(crate) bindings: Vec<ShaderBinding>,
+}
+
+/// Compute entry points of `module`.
+pub(crate) fn compute_entry_points(module: &Module) -> Vec<ComputeEntryPoint> {
+    module
+        .entry_points
+        .iter()
+        .filter(|entry_point| entry_point.stage == ShaderStage::Compute)
+        .map(|entry_point| {
+            let overrides = entry_point.workgroup_size_overrides.unwrap_or_default();
+            ComputeEntryPoint {
+                name: entry_point.name.clone(),
+                workgroup_size: std::array::from_fn(|axis| match overrides[axis] {
+                    Some(expression) => WorkgroupSize::of(module, expression),
+                    None => Some(WorkgroupSize::Fixed(entry_point.workgroup_size[axis])),
+                }),
+                bindings: entry_point_bindings(module, &entry_point.function),
+            }
+        })
+        .collect()
+}
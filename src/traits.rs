@@ -33,10 +33,23 @@ pub trait ComputeShader: TypePath + Send + Sync + 'static {
     fn shader_defs<'a>() -> &'a [ShaderDefVal] {
         &[]
     }
+
+    /// Push constant ranges of the pipeline layout.
+    ///
+    /// Their data is set per pass with `set_push_constants()`, either from the
+    /// builder or from the worker. wgpu doesn't reflect push constants, so shaders
+    /// using them must declare their [`layouts`](Self::layouts) too.
     fn push_constant_ranges<'a>() -> &'a [PushConstantRange] {
         &[]
     }
 
+    /// Values of the pipeline-overridable constants (WGSL `override` declarations).
+    ///
+    /// They can be overridden per pass with `add_pass_with_constants()`.
+    fn constants<'a>() -> &'a [(&'a str, f64)] {
+        &[]
+    }
+
     /// By default, the shader entry point is `main`.
     /// You can change it from here.
     fn entry_point<'a>() -> &'a str {
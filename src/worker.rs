@@ -1,26 +1,59 @@
 use core::panic;
 use std::{
+    any::type_name,
+    collections::{BTreeMap, VecDeque},
+    fmt,
     marker::PhantomData,
-    ops::Deref,
-    time::{Duration, SystemTime},
+    ops::{Deref, Range},
+    sync::{
+        Arc, OnceLock,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant, SystemTime},
 };
 
 use bevy::{
-    platform::collections::HashMap,
-    prelude::{Res, ResMut, Resource},
+    asset::Handle,
+    diagnostic::FrameCount,
+    image::Image,
+    platform::collections::{HashMap, HashSet},
+    prelude::{AssetServer, EventWriter, Mut, Res, ResMut, Resource, World},
     render::{
-        render_resource::{Buffer, ComputePipeline},
+        render_resource::{
+            Buffer, BufferDescriptor, ComputePipeline, ComputePipelineDescriptor, Sampler,
+            ShaderType, Texture, TextureView,
+            encase::{StorageBuffer, UniformBuffer, private::WriteInto},
+        },
         renderer::{RenderDevice, RenderQueue},
     },
 };
-use bytemuck::{AnyBitPattern, NoUninit, bytes_of, cast_slice, from_bytes};
-use wgpu::{BindGroupEntry, CommandEncoder, CommandEncoderDescriptor, ComputePassDescriptor};
+use bytemuck::{
+    AnyBitPattern, NoUninit, Pod, bytes_of, cast_slice, cast_slice_mut, from_bytes,
+    pod_read_unaligned,
+};
+use parking_lot::Mutex;
+use tracing::warn;
+use wgpu::{
+    BindGroupEntry, BindingResource, BufferUsages, COPY_BUFFER_ALIGNMENT, CommandEncoder,
+    CommandEncoderDescriptor, ComputePassDescriptor, Extent3d, Features, ShaderStages,
+    TexelCopyBufferInfo, TexelCopyBufferLayout, TextureUsages, util::BufferInitDescriptor,
+};
 
 use crate::{
+    bind_groups::{BindGroupCache, BindGroupKey, ResourceId},
+    diagnostics::FrameStats,
     error::{Error, Result},
-    pipeline_cache::{AppCachedComputePipelineId, PipelineCache},
-    traits::ComputeWorker,
-    worker_builder::AppComputeWorkerBuilder,
+    events::{ComputePipelineReloaded, ComputeWorkerError, ComputeWorkerFinished},
+    images::ComputeImages,
+    jobs::{ComputeJob, ComputeJobHandle, ComputeJobOutput, PendingJob},
+    pipeline_cache::{AppCachedComputePipelineId, CachedPipelineState, Constants, PipelineCache},
+    profiling::{PendingTimestamps, RunTimings, TimestampQueries},
+    reflection::{BindingKind, ShaderBinding},
+    traits::{ComputeShader, ComputeWorker},
+    worker_builder::{
+        AppComputeWorkerBuilder, pass_constants, pipeline_descriptor, pipeline_key,
+        same_texture_descriptor, vars_bindings,
+    },
 };
 
 #[derive(PartialEq, Clone, Copy)]
@@ -29,31 +62,357 @@ pub enum RunMode {
     OneShot(bool),
 }
 
+/// When a staging buffer copies the buffer or texture it reads back.
+///
+/// Runs which resolve a [job](AppComputeWorker::submit_job) read back every
+/// staging buffer, whatever their policy.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReadbackPolicy {
+    /// Every time the worker runs.
+    #[default]
+    EveryRun,
+    /// Every `n` runs, starting with the first one.
+    EveryNRuns(u32),
+    /// Only on the run following [`AppComputeWorker::request_readback`].
+    OnRequest,
+}
+
+/// Whether the pipeline of a compute pass can run, see
+/// [`AppComputeWorker::pipeline_state`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PipelineState {
+    /// The shader is still being processed, or the pipeline created.
+    Queued,
+    /// The pipeline is created, the pass runs.
+    Ready,
+    /// The pass can't run, see [`AppComputeWorker::pipeline_error`].
+    ///
+    /// Once a pass ran, it keeps running the previous version of its shader if a
+    /// new one fails to compile.
+    Failed,
+}
+
 #[derive(Debug, PartialEq)]
 pub enum WorkerState {
     Created,
     Available,
-    Working { start_time: SystemTime },
+    Working {
+        start_time: SystemTime,
+    },
     FinishedWorking,
+    /// The last run failed, see [`ComputeWorkerError`].
+    Failed,
+}
+
+/// Identifies a step of a worker, either by its index or by its label.
+///
+/// Steps are indexed in the order they were added to the builder, and labeled
+/// with [`AppComputeWorkerBuilder::label`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StepId {
+    Index(usize),
+    Label(String),
+}
+
+impl From<usize> for StepId {
+    fn from(index: usize) -> Self {
+        Self::Index(index)
+    }
+}
+
+impl From<&str> for StepId {
+    fn from(label: &str) -> Self {
+        Self::Label(label.to_owned())
+    }
+}
+
+impl From<String> for StepId {
+    fn from(label: String) -> Self {
+        Self::Label(label)
+    }
+}
+
+impl fmt::Display for StepId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StepId::Index(index) => write!(f, "#{index}"),
+            StepId::Label(label) => write!(f, "`{label}`"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Step {
+    pub(crate) label: Option<String>,
+    /// Whether runs record the step, see [`AppComputeWorker::set_step_enabled`].
+    pub(crate) enabled: bool,
+    pub(crate) kind: StepKind,
+}
+
+impl Step {
+    /// Identifies the step at `index` by its label if it has one.
+    pub(crate) fn id(&self, index: usize) -> StepId {
+        match &self.label {
+            Some(label) => StepId::Label(label.clone()),
+            None => StepId::Index(index),
+        }
+    }
+
+    /// Name of the step in GPU captures: its label, or the shader of a pass.
+    fn debug_label(&self) -> &str {
+        match (&self.label, &self.kind) {
+            (Some(label), _) => label,
+            (None, StepKind::ComputePass(compute_pass)) => &compute_pass.shader_type_path,
+            (None, StepKind::Swap(_, _)) => "swap",
+        }
+    }
+
+    /// Whether the step binds, swaps or reads its workgroup counts from `name`.
+    fn uses(&self, name: &str) -> bool {
+        match &self.kind {
+            StepKind::ComputePass(compute_pass) => {
+                compute_pass
+                    .bindings
+                    .iter()
+                    .any(|binding| binding.name == name)
+                    || matches!(&compute_pass.dispatch, Dispatch::Indirect { buffer, .. } if buffer == name)
+            }
+            StepKind::Swap(a, b) => a == name || b == name,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
-pub(crate) enum Step {
+pub(crate) enum StepKind {
     ComputePass(ComputePass),
     Swap(String, String),
 }
 
+/// How many workgroups a [`ComputePass`] dispatches.
+#[derive(Clone, Debug)]
+pub(crate) enum Dispatch {
+    Direct([u32; 3]),
+    /// Read the workgroup counts from `buffer`, at `offset` bytes.
+    Indirect {
+        buffer: String,
+        offset: u64,
+    },
+    /// Workgroup counts evaluated by `update` before every run.
+    Dynamic {
+        workgroups: [u32; 3],
+        update: WorkgroupsFn,
+    },
+    /// Enough workgroups to cover `elements` invocations, computed from the workgroup
+    /// size of the shader once its pipeline is ready.
+    Elements {
+        elements: [u32; 3],
+        workgroups: Option<[u32; 3]>,
+    },
+}
+
+type WorkgroupsUpdate = dyn Fn(&World) -> [u32; 3] + Send + Sync;
+
+impl Dispatch {
+    /// Workgroup counts known on the CPU, if any.
+    fn workgroups(&self) -> Option<[u32; 3]> {
+        match self {
+            Dispatch::Direct(workgroups) | Dispatch::Dynamic { workgroups, .. } => {
+                Some(*workgroups)
+            }
+            Dispatch::Elements { workgroups, .. } => *workgroups,
+            Dispatch::Indirect { .. } => None,
+        }
+    }
+}
+
+/// Size of the `[x, y, z]` workgroup counts read by an indirect dispatch.
+const INDIRECT_ARGS_SIZE: u64 = 12;
+
+/// Whether an indirect dispatch can read its workgroup counts from `buffer` at `offset`.
+pub(crate) fn fits_indirect_args(buffer: &Buffer, offset: u64) -> bool {
+    offset.is_multiple_of(4)
+        && offset
+            .checked_add(INDIRECT_ARGS_SIZE)
+            .is_some_and(|end| end <= buffer.size())
+        && buffer.usage().contains(BufferUsages::INDIRECT)
+}
+
+/// Byte offsets of the elements of type `T` in `range` of `target`.
+fn element_bytes<T>(target: &str, range: &Range<usize>) -> Result<Range<u64>> {
+    let size = size_of::<T>() as u64;
+    if size == 0 {
+        return Err(Error::ZeroSizedType(target.to_owned()));
+    }
+
+    match (
+        (range.start as u64).checked_mul(size),
+        (range.end as u64).checked_mul(size),
+    ) {
+        (Some(start), Some(end)) => Ok(start..end),
+        _ => Err(Error::RangeOverflow(target.to_owned())),
+    }
+}
+
+/// Computes the workgroup counts of a pass from the main world.
+#[derive(Clone)]
+pub(crate) struct WorkgroupsFn(pub(crate) Arc<WorkgroupsUpdate>);
+
+impl fmt::Debug for WorkgroupsFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("WorkgroupsFn")
+    }
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct ComputePass {
-    pub(crate) workgroups: [u32; 3],
-    pub(crate) vars: Vec<String>,
+    pub(crate) dispatch: Dispatch,
+    pub(crate) bindings: Vec<PassBinding>,
+    /// Whether `bindings` are matched by name with the globals of the shader, once reflected.
+    pub(crate) bind_by_name: bool,
     pub(crate) shader_type_path: String,
+    /// Key of the pipeline, which also depends on the pipeline-overridable constants.
+    pub(crate) pipeline_key: String,
+    /// Bytes spanned by the push constant ranges of the shader visible to compute shaders.
+    pub(crate) push_constant_range: Option<Range<u32>>,
+    /// Push constants of the pass, filling `push_constant_range`.
+    pub(crate) push_constants: Option<Vec<u8>>,
+}
+
+impl ComputePass {
+    /// Bytes spanned by the push constant ranges of `S` visible to compute shaders.
+    pub(crate) fn push_constant_range<S: ComputeShader>() -> Option<Range<u32>> {
+        S::push_constant_ranges()
+            .iter()
+            .filter(|range| range.stages.contains(ShaderStages::COMPUTE))
+            .map(|range| range.range.clone())
+            .reduce(|a, b| a.start.min(b.start)..a.end.max(b.end))
+    }
+
+    /// Whether `size` bytes of push constants exactly fill the range of the shader.
+    pub(crate) fn fits_push_constants(&self, size: usize) -> bool {
+        self.push_constant_range
+            .as_ref()
+            .is_some_and(|range| range.len() == size)
+    }
+}
+
+/// A worker resource bound to a [`ComputePass`].
+#[derive(Clone, Debug)]
+pub(crate) struct PassBinding {
+    pub(crate) group: u32,
+    pub(crate) binding: u32,
+    pub(crate) name: String,
 }
 
 #[derive(Clone, Debug)]
 pub(crate) struct StagingBuffer {
-    pub(crate) mapped: bool,
-    pub(crate) buffer: Buffer,
+    /// One buffer per frame in flight, along with whether it's mapped.
+    pub(crate) frames: Vec<(Buffer, bool)>,
+    /// Frame in flight holding the most recent readback, if it's still mapped.
+    pub(crate) read_frame: Option<usize>,
+    /// Set when this staging buffer reads back a texture instead of a buffer.
+    pub(crate) texture_layout: Option<TextureCopyLayout>,
+    pub(crate) policy: ReadbackPolicy,
+    /// Offset of the bytes read back, when only a range of the buffer is.
+    pub(crate) offset: u64,
+    /// Whether the next run reads this buffer back, whatever its policy.
+    pub(crate) requested: bool,
+}
+
+impl StagingBuffer {
+    /// Wrap `buffer`, which must be mapped at creation.
+    ///
+    /// Nothing can be read from it until a run copied to it.
+    pub(crate) fn new(buffer: Buffer, texture_layout: Option<TextureCopyLayout>) -> Self {
+        Self {
+            frames: vec![(buffer, true)],
+            read_frame: None,
+            texture_layout,
+            policy: ReadbackPolicy::default(),
+            offset: 0,
+            requested: false,
+        }
+    }
+
+    /// Whether run number `run` copies to this staging buffer.
+    fn is_due(&self, run: u64) -> bool {
+        self.requested
+            || match self.policy {
+                ReadbackPolicy::EveryRun => true,
+                ReadbackPolicy::EveryNRuns(n) => run.is_multiple_of(n.max(1) as u64),
+                ReadbackPolicy::OnRequest => false,
+            }
+    }
+
+    /// Allocate copies of the first buffer until there's one for each of `frames`.
+    fn allocate_frames(&mut self, render_device: &RenderDevice, label: &str, frames: usize) {
+        let size = self.frames[0].0.size();
+        while self.frames.len() < frames {
+            let buffer = render_device.create_buffer(&BufferDescriptor {
+                label: Some(label),
+                size,
+                usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+                mapped_at_creation: true,
+            });
+            self.frames.push((buffer, true));
+        }
+    }
+}
+
+/// A run of the worker submitted to the GPU.
+struct Submission {
+    /// Frame in flight whose staging buffers the run copies its results to.
+    frame: usize,
+    start_time: SystemTime,
+    /// When the GPU completed the run.
+    end_time: Arc<OnceLock<SystemTime>>,
+    /// Callbacks left before the results can be read: one per staging buffer, one
+    /// for the timestamps if the worker is profiled, and one for the run itself.
+    pending: Arc<AtomicUsize>,
+    /// Staging buffers the run copies to.
+    readbacks: Vec<String>,
+    /// Staging buffers resized while the run was in flight, kept until it finishes.
+    retired: Vec<Buffer>,
+    timestamps: Option<PendingTimestamps>,
+    job: Option<PendingJob>,
+}
+
+/// Row layout of a texture copied into a staging buffer.
+///
+/// Texture to buffer copies require rows to be aligned to
+/// [`wgpu::COPY_BYTES_PER_ROW_ALIGNMENT`], so the staging buffer may hold
+/// padding at the end of each row.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TextureCopyLayout {
+    pub(crate) size: Extent3d,
+    pub(crate) bytes_per_row: u32,
+    pub(crate) padded_bytes_per_row: u32,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct WorkerTexture {
+    pub(crate) texture: Texture,
+    pub(crate) view: TextureView,
+}
+
+/// How a worker buffer may be bound, depending on the function which created it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BufferKind {
+    /// A uniform buffer, see [`AppComputeWorkerBuilder::add_uniform`].
+    Uniform,
+    /// A storage buffer, see [`AppComputeWorkerBuilder::add_storage`] and
+    /// [`AppComputeWorkerBuilder::add_rw_storage`].
+    Storage { read_only: bool },
+}
+
+impl fmt::Display for BufferKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BufferKind::Uniform => write!(f, "uniform buffer"),
+            BufferKind::Storage { read_only: true } => write!(f, "read-only storage buffer"),
+            BufferKind::Storage { read_only: false } => write!(f, "read/write storage buffer"),
+        }
+    }
 }
 
 /// Struct to manage data transfers from/to the GPU
@@ -67,14 +426,53 @@ pub struct AppComputeWorker<W: ComputeWorker> {
     pub(crate) state: WorkerState,
     render_device: RenderDevice,
     render_queue: RenderQueue,
+    asset_server: AssetServer,
     cached_pipeline_ids: HashMap<String, AppCachedComputePipelineId>,
+    /// Pipelines of passes inserted at runtime, queued on the next frame.
+    queued_pipelines: Vec<(String, ComputePipelineDescriptor, Constants)>,
     pipelines: HashMap<String, Option<ComputePipeline>>,
     buffers: HashMap<String, Buffer>,
+    buffer_kinds: HashMap<String, BufferKind>,
+    textures: HashMap<String, WorkerTexture>,
+    samplers: HashMap<String, Sampler>,
+    images: HashMap<String, Handle<Image>>,
+    compute_images: ComputeImages,
     staging_buffers: HashMap<String, StagingBuffer>,
     steps: Vec<Step>,
+    bind_groups: BindGroupCache,
+    /// Resources used by the shader of each pipeline, once reflected.
+    shader_bindings: HashMap<String, Vec<ShaderBinding>>,
+    /// Shader and compilation error of each pipeline which couldn't be created, by
+    /// pipeline key, so variants of a shader with other constants don't overwrite them.
+    shader_errors: HashMap<String, (String, String)>,
     command_encoder: Option<CommandEncoder>,
     run_mode: RunMode,
-    submission_queue_processed: bool,
+    /// Whether errors panic instead of being sent as [`ComputeWorkerError`] events.
+    strict: bool,
+    /// Usages added to the buffers created by [`Self::try_add_buffer`], see
+    /// [`AppComputeWorkerBuilder::set_extra_buffer_usages`].
+    extra_buffer_usages: Option<BufferUsages>,
+    /// Jobs waiting for their turn to run.
+    jobs: VecDeque<PendingJob>,
+    /// Job whose inputs were written, resolved once the run it's submitted with finishes.
+    current_job: Option<PendingJob>,
+    /// Runs submitted to the GPU, oldest first.
+    submissions: VecDeque<Submission>,
+    /// How many runs may be submitted before the oldest one finished.
+    frames_in_flight: usize,
+    /// Frame in flight the next run copies its results to.
+    next_frame: usize,
+    /// How many runs were submitted, to pick the staging buffers read back by the next one.
+    runs: u64,
+    /// Staging buffers which couldn't be mapped, along with their frame and the reason.
+    map_errors: Arc<Mutex<Vec<(usize, String, String)>>>,
+    /// Whether the timings of every run are measured, see [`Self::timings`].
+    profile: bool,
+    /// Queries timing every pass, when profiling on a device supporting them.
+    timestamps: Option<TimestampQueries>,
+    timings: Option<RunTimings>,
+    /// What the worker did this frame, measured as diagnostics.
+    pub(crate) stats: FrameStats,
     /// Maximum duration the compute shader will run asyncronously before being set to synchronous.
     ///
     /// 0 seconds means the shader will immediately be polled synchronously. None emeans the shader will only run asynchronously.
@@ -91,26 +489,67 @@ impl<W: ComputeWorker> From<&AppComputeWorkerBuilder<'_, W>> for AppComputeWorke
         let pipelines = builder
             .cached_pipeline_ids
             .iter()
-            .map(|(type_path, _)| (type_path.clone(), None))
+            .map(|(pipeline_key, _)| (pipeline_key.clone(), None))
             .collect();
 
-        let command_encoder =
-            Some(render_device.create_command_encoder(&CommandEncoderDescriptor { label: None }));
+        let frames_in_flight = builder.frames_in_flight.max(1);
+
+        let timestamps = (builder.profile
+            && render_device.features().contains(Features::TIMESTAMP_QUERY))
+        .then(|| {
+            let passes = builder
+                .steps
+                .iter()
+                .filter(|step| matches!(step.kind, StepKind::ComputePass(_)))
+                .count();
+            TimestampQueries::new(
+                &render_device,
+                &render_queue,
+                passes as u32,
+                frames_in_flight,
+            )
+        });
+        let mut staging_buffers = builder.staging_buffers.clone();
+        for (name, staging_buffer) in staging_buffers.iter_mut() {
+            staging_buffer.allocate_frames(&render_device, name, frames_in_flight);
+        }
 
         Self {
             state: WorkerState::Created,
             render_device,
             render_queue,
+            asset_server: builder.world.resource::<AssetServer>().clone(),
             cached_pipeline_ids: builder.cached_pipeline_ids.clone(),
+            queued_pipelines: vec![],
             pipelines,
             buffers: builder.buffers.clone(),
-            staging_buffers: builder.staging_buffers.clone(),
+            buffer_kinds: builder.buffer_kinds.clone(),
+            textures: builder.textures.clone(),
+            samplers: builder.samplers.clone(),
+            images: builder.images.clone(),
+            compute_images: builder.world.resource::<ComputeImages>().clone(),
+            staging_buffers,
             steps: builder.steps.clone(),
-            command_encoder,
+            bind_groups: BindGroupCache::default(),
+            shader_bindings: HashMap::default(),
+            shader_errors: HashMap::default(),
+            command_encoder: None,
             run_mode: builder.run_mode,
+            strict: builder.strict,
+            extra_buffer_usages: builder.extra_buffer_usages,
+            jobs: VecDeque::new(),
+            current_job: None,
+            submissions: VecDeque::new(),
+            frames_in_flight,
+            next_frame: 0,
+            runs: 0,
+            map_errors: Arc::default(),
+            profile: builder.profile,
+            timestamps,
+            timings: None,
+            stats: FrameStats::default(),
             _phantom: PhantomData,
             maximum_async_time: builder.maximum_async_time,
-            submission_queue_processed: false,
         }
     }
 }
@@ -118,67 +557,379 @@ impl<W: ComputeWorker> From<&AppComputeWorkerBuilder<'_, W>> for AppComputeWorke
 impl<W: ComputeWorker> AppComputeWorker<W> {
     #[inline]
     fn dispatch(&mut self, index: usize) -> Result<()> {
-        let compute_pass = match &self.steps[index] {
-            Step::ComputePass(compute_pass) => compute_pass,
-            Step::Swap(_, _) => return Err(Error::InvalidStep(format!("{:?}", self.steps[index]))),
+        let compute_pass = match &self.steps[index].kind {
+            StepKind::ComputePass(compute_pass) => compute_pass,
+            StepKind::Swap(_, _) => {
+                return Err(Error::InvalidStep(format!("{:?}", self.steps[index])));
+            }
         };
 
-        let mut entries = vec![];
-        for (index, var) in compute_pass.vars.iter().enumerate() {
-            let Some(buffer) = self.buffers.get(var) else {
-                return Err(Error::BufferNotFound(var.to_owned()));
-            };
-
-            let entry = BindGroupEntry {
-                binding: index as u32,
-                resource: buffer.as_entire_binding(),
-            };
-
-            entries.push(entry);
-        }
-
-        let Some(maybe_pipeline) = self.pipelines.get(&compute_pass.shader_type_path) else {
+        let Some(maybe_pipeline) = self.pipelines.get(&compute_pass.pipeline_key) else {
             return Err(Error::PipelinesEmpty);
         };
 
         let Some(pipeline) = maybe_pipeline else {
+            if let Some((_, message)) = self.shader_errors.get(&compute_pass.pipeline_key) {
+                return Err(Error::PipelineFailed {
+                    shader: compute_pass.shader_type_path.clone(),
+                    message: message.clone(),
+                });
+            }
+
             return Err(Error::PipelineNotReady);
         };
 
-        let bind_group_layout = pipeline.get_bind_group_layout(0);
-        let bind_group =
-            self.render_device
-                .create_bind_group(None, &bind_group_layout.into(), &entries);
+        match &compute_pass.dispatch {
+            Dispatch::Indirect { buffer, offset } => match self.buffers.get(buffer) {
+                None => return Err(Error::BufferNotFound(buffer.to_owned())),
+                Some(args) if !fits_indirect_args(args, *offset) => {
+                    return Err(Error::InvalidIndirectArgs {
+                        step: self.steps[index].id(index).to_string(),
+                        buffer: buffer.to_owned(),
+                        offset: *offset,
+                        size: args.size(),
+                        usage: args.usage(),
+                    });
+                }
+                Some(_) => {}
+            },
+            Dispatch::Elements {
+                workgroups: None, ..
+            } => {
+                return Err(Error::WorkgroupSizeUnknown(
+                    compute_pass.shader_type_path.clone(),
+                ));
+            }
+            _ => {}
+        }
+
+        if compute_pass.bind_by_name
+            && !self
+                .shader_bindings
+                .contains_key(&compute_pass.pipeline_key)
+        {
+            return Err(Error::BindingsNotReflected(
+                compute_pass.shader_type_path.clone(),
+            ));
+        }
+
+        self.validate_layout(index, compute_pass)?;
+
+        let mut groups: BTreeMap<u32, Vec<&PassBinding>> = BTreeMap::new();
+        for binding in &compute_pass.bindings {
+            groups.entry(binding.group).or_default().push(binding);
+        }
+
+        let mut bind_groups = vec![];
+        for (group, bindings) in groups {
+            let mut resources = vec![];
+            for binding in &bindings {
+                let Some(resource) = self.resource_id(&binding.name) else {
+                    if compute_pass.bind_by_name {
+                        return Err(Error::UnmatchedBinding {
+                            shader: compute_pass.shader_type_path.clone(),
+                            name: binding.name.to_owned(),
+                        });
+                    }
+                    return Err(Error::BufferNotFound(binding.name.to_owned()));
+                };
+
+                resources.push(resource);
+            }
+
+            let key = BindGroupKey {
+                pipeline: pipeline.id(),
+                resources,
+            };
+
+            let bind_group = match self.bind_groups.get(index, group, &key) {
+                Some(bind_group) => bind_group.clone(),
+                None => {
+                    self.validate_bindings(index, compute_pass)?;
+
+                    let entries = bindings
+                        .iter()
+                        .filter_map(|binding| {
+                            Some(BindGroupEntry {
+                                binding: binding.binding,
+                                resource: self.binding_resource(&binding.name)?,
+                            })
+                        })
+                        .collect::<Vec<_>>();
+
+                    let bind_group_layout = pipeline.get_bind_group_layout(group);
+                    let label = format!("{} @group({group})", self.steps[index].debug_label());
+                    let bind_group = self.render_device.create_bind_group(
+                        label.as_str(),
+                        &bind_group_layout.into(),
+                        &entries,
+                    );
+
+                    self.bind_groups
+                        .insert(index, group, key, bind_group.clone());
+                    bind_group
+                }
+            };
 
+            bind_groups.push((group, bind_group));
+        }
+
+        let label = self.steps[index].debug_label();
         let Some(encoder) = &mut self.command_encoder else {
             return Err(Error::EncoderIsNone);
         };
+        let timestamp_writes = self
+            .timestamps
+            .as_mut()
+            .map(|timestamps| timestamps.begin_pass(self.steps[index].id(index)));
+        encoder.push_debug_group(label);
         {
             let mut cpass = encoder.begin_compute_pass(&ComputePassDescriptor {
-                label: None,
-                timestamp_writes: None,
+                label: Some(label),
+                timestamp_writes,
             });
             cpass.set_pipeline(pipeline);
-            cpass.set_bind_group(0, &bind_group, &[]);
-            cpass.dispatch_workgroups(
-                compute_pass.workgroups[0],
-                compute_pass.workgroups[1],
-                compute_pass.workgroups[2],
-            )
+            for (group, bind_group) in &bind_groups {
+                cpass.set_bind_group(*group, bind_group, &[]);
+            }
+            if let (Some(range), Some(push_constants)) = (
+                &compute_pass.push_constant_range,
+                &compute_pass.push_constants,
+            ) {
+                cpass.set_push_constants(range.start, push_constants);
+            }
+            if let Dispatch::Indirect { buffer, offset } = &compute_pass.dispatch {
+                cpass.dispatch_workgroups_indirect(&self.buffers[buffer], *offset);
+            } else if let Some(workgroups) = compute_pass.dispatch.workgroups() {
+                cpass.dispatch_workgroups(workgroups[0], workgroups[1], workgroups[2]);
+            }
+        }
+        encoder.pop_debug_group();
+
+        Ok(())
+    }
+
+    /// Bind the latest [`GpuImage`](bevy::render::texture::GpuImage) texture of each image.
+    #[inline]
+    fn sync_images(&mut self) -> Result<()> {
+        for (name, image) in &self.images {
+            let Some(texture) = self.compute_images.get(image.id()) else {
+                return Err(Error::ImageNotReady(name.to_owned()));
+            };
+
+            self.textures.insert(name.to_owned(), texture);
+        }
+
+        Ok(())
+    }
+
+    /// Make sure the pass at `index` binds a resource to every variable its shader
+    /// uses, and nothing else.
+    ///
+    /// Shaders which couldn't be reflected aren't validated.
+    fn validate_layout(&self, index: usize, compute_pass: &ComputePass) -> Result<()> {
+        let Some(shader_bindings) = self.shader_bindings.get(&compute_pass.pipeline_key) else {
+            return Ok(());
+        };
+
+        if let Some(shader_binding) = shader_bindings.iter().find(|shader_binding| {
+            !compute_pass.bindings.iter().any(|binding| {
+                binding.group == shader_binding.group && binding.binding == shader_binding.binding
+            })
+        }) {
+            return Err(Error::MissingBinding {
+                shader: compute_pass.shader_type_path.clone(),
+                group: shader_binding.group,
+                binding: shader_binding.binding,
+                name: shader_binding.name.clone(),
+            });
+        }
+
+        if let Some(binding) = compute_pass.bindings.iter().find(|binding| {
+            !shader_bindings.iter().any(|shader_binding| {
+                binding.group == shader_binding.group && binding.binding == shader_binding.binding
+            })
+        }) {
+            return Err(Error::UnexpectedBinding {
+                pass: self.steps[index].id(index).to_string(),
+                shader: compute_pass.shader_type_path.clone(),
+                group: binding.group,
+                binding: binding.binding,
+                name: binding.name.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Make sure the resources bound by the pass at `index` have the kind, access and
+    /// size the shader expects.
+    ///
+    /// Shaders which couldn't be reflected aren't validated.
+    fn validate_bindings(&self, index: usize, compute_pass: &ComputePass) -> Result<()> {
+        let Some(shader_bindings) = self.shader_bindings.get(&compute_pass.pipeline_key) else {
+            return Ok(());
+        };
+
+        for shader_binding in shader_bindings {
+            // Checked by `validate_layout` before every dispatch.
+            let Some(binding) = compute_pass.bindings.iter().find(|binding| {
+                binding.group == shader_binding.group && binding.binding == shader_binding.binding
+            }) else {
+                continue;
+            };
+
+            if let Some(actual) = self.binding_mismatch(shader_binding.kind, &binding.name) {
+                return Err(Error::BindingMismatch {
+                    pass: self.steps[index].id(index).to_string(),
+                    shader: compute_pass.shader_type_path.clone(),
+                    group: shader_binding.group,
+                    binding: shader_binding.binding,
+                    name: shader_binding.name.clone(),
+                    expected: shader_binding.kind,
+                    actual,
+                });
+            }
         }
 
         Ok(())
     }
 
+    /// Description of the resource called `name` if it can't be bound where the
+    /// shader expects `kind`.
+    fn binding_mismatch(&self, kind: BindingKind, name: &str) -> Option<String> {
+        if let Some(buffer) = self.buffers.get(name) {
+            let buffer_kind = self.buffer_kinds.get(name).copied().unwrap_or(
+                if buffer.usage().contains(BufferUsages::UNIFORM) {
+                    BufferKind::Uniform
+                } else {
+                    BufferKind::Storage { read_only: false }
+                },
+            );
+
+            let matches = match (kind, buffer_kind) {
+                (BindingKind::Uniform { min_size }, BufferKind::Uniform) => {
+                    buffer.size() >= min_size
+                }
+                (
+                    BindingKind::Storage {
+                        read_only,
+                        min_size,
+                    },
+                    BufferKind::Storage {
+                        read_only: buffer_read_only,
+                    },
+                ) => {
+                    // wgpu lets shaders write to any storage buffer, only strict workers
+                    // hold them to the access they were added with.
+                    let writes_read_only = !read_only && buffer_read_only;
+                    if writes_read_only && !self.strict {
+                        warn!(
+                            "`{name}` was added with `add_storage` (read only), but is bound to a `read_write` variable."
+                        );
+                    }
+                    (!writes_read_only || !self.strict) && buffer.size() >= min_size
+                }
+                (BindingKind::Other, _) => true,
+                _ => false,
+            };
+
+            return (!matches)
+                .then(|| format!("{buffer_kind} `{name}` of {} bytes", buffer.size()));
+        }
+
+        if let Some(texture) = self.textures.get(name) {
+            let usage = texture.texture.usage();
+            let matches = match kind {
+                BindingKind::Texture => usage.contains(TextureUsages::TEXTURE_BINDING),
+                BindingKind::StorageTexture => usage.contains(TextureUsages::STORAGE_BINDING),
+                BindingKind::Other => true,
+                _ => false,
+            };
+
+            return (!matches).then(|| format!("texture `{name}` with usage {usage:?}"));
+        }
+
+        if self.samplers.contains_key(name) {
+            let matches = matches!(kind, BindingKind::Sampler | BindingKind::Other);
+            return (!matches).then(|| format!("sampler `{name}`"));
+        }
+
+        None
+    }
+
+    /// Id of the buffer, texture or sampler called `name`.
+    #[inline]
+    fn resource_id(&self, name: &str) -> Option<ResourceId> {
+        if let Some(buffer) = self.buffers.get(name) {
+            return Some(ResourceId::Buffer(buffer.id()));
+        }
+
+        if let Some(texture) = self.textures.get(name) {
+            return Some(ResourceId::TextureView(texture.view.id()));
+        }
+
+        self.samplers
+            .get(name)
+            .map(|sampler| ResourceId::Sampler(sampler.id()))
+    }
+
+    /// Find the buffer, texture or sampler called `name`.
+    #[inline]
+    fn binding_resource(&self, name: &str) -> Option<BindingResource<'_>> {
+        if let Some(buffer) = self.buffers.get(name) {
+            return Some(buffer.as_entire_binding());
+        }
+
+        if let Some(texture) = self.textures.get(name) {
+            return Some(BindingResource::TextureView(&texture.view));
+        }
+
+        self.samplers
+            .get(name)
+            .map(|sampler| BindingResource::Sampler(sampler))
+    }
+
     #[inline]
     fn swap(&mut self, index: usize) -> Result<()> {
-        let (buf_a_name, buf_b_name) = match &self.steps[index] {
-            Step::ComputePass(_) => {
+        let (buf_a_name, buf_b_name) = match &self.steps[index].kind {
+            StepKind::ComputePass(_) => {
                 return Err(Error::InvalidStep(format!("{:?}", self.steps[index])));
             }
-            Step::Swap(a, b) => (a.as_str(), b.as_str()),
+            StepKind::Swap(a, b) => (a.as_str(), b.as_str()),
         };
 
+        if self.textures.contains_key(buf_a_name) {
+            if !self.textures.contains_key(buf_b_name) {
+                return Err(Error::TextureNotFound(buf_b_name.to_owned()));
+            }
+
+            let [Some(texture_a), Some(texture_b)] =
+                self.textures.get_many_mut([buf_a_name, buf_b_name])
+            else {
+                panic!("get_many_mut(): returned None texture.")
+            };
+
+            // Images are bound again before every run, swap their handles too so that the
+            // swap sticks. Their descriptors are only known once they're loaded.
+            if let [Some(image_a), Some(image_b)] =
+                self.images.get_many_mut([buf_a_name, buf_b_name])
+            {
+                if !same_texture_descriptor(texture_a, texture_b) {
+                    return Err(Error::SwapKindMismatch {
+                        step: self.steps[index].id(index).to_string(),
+                        a: buf_a_name.to_owned(),
+                        b: buf_b_name.to_owned(),
+                    });
+                }
+                std::mem::swap(image_a, image_b);
+            }
+
+            std::mem::swap(texture_a, texture_b);
+
+            return Ok(());
+        }
+
         if !self.buffers.contains_key(buf_a_name) {
             return Err(Error::BufferNotFound(buf_a_name.to_owned()));
         }
@@ -194,64 +945,178 @@ impl<W: ComputeWorker> AppComputeWorker<W> {
 
         std::mem::swap(buffer_a, buffer_b);
 
+        let kind_a = self.buffer_kinds.remove(buf_a_name);
+        let kind_b = self.buffer_kinds.remove(buf_b_name);
+        if let Some(kind) = kind_a {
+            self.buffer_kinds.insert(buf_b_name.to_owned(), kind);
+        }
+        if let Some(kind) = kind_b {
+            self.buffer_kinds.insert(buf_a_name.to_owned(), kind);
+        }
+
         Ok(())
     }
 
+    /// Names of the staging buffers the next run copies to.
+    fn due_readbacks(&self) -> Vec<String> {
+        let job = self.current_job.is_some();
+        self.staging_buffers
+            .iter()
+            .filter(|(_, staging_buffer)| job || staging_buffer.is_due(self.runs))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Copy the buffers and textures in `readbacks` to their staging buffers of the
+    /// next frame in flight.
     #[inline]
-    fn read_staging_buffers(&mut self) -> Result<&mut Self> {
-        for (name, staging_buffer) in &self.staging_buffers {
+    fn read_staging_buffers(&mut self, readbacks: &[String]) -> Result<&mut Self> {
+        let frame = self.next_frame;
+        for name in readbacks {
+            let Some(staging_buffer) = self.staging_buffers.get(name) else {
+                return Err(Error::StagingBufferNotFound(name.to_owned()));
+            };
+            let staging = &staging_buffer.frames[frame].0;
             let Some(encoder) = &mut self.command_encoder else {
                 return Err(Error::EncoderIsNone);
             };
+
+            if let Some(layout) = staging_buffer.texture_layout {
+                let Some(texture) = self.textures.get(name) else {
+                    return Err(Error::TextureNotFound(name.to_owned()));
+                };
+
+                encoder.copy_texture_to_buffer(
+                    texture.texture.as_image_copy(),
+                    TexelCopyBufferInfo {
+                        buffer: staging,
+                        layout: TexelCopyBufferLayout {
+                            offset: 0,
+                            bytes_per_row: Some(layout.padded_bytes_per_row),
+                            rows_per_image: Some(layout.size.height),
+                        },
+                    },
+                    layout.size,
+                );
+                continue;
+            }
+
             let Some(buffer) = self.buffers.get(name) else {
                 return Err(Error::BufferNotFound(name.to_owned()));
             };
 
             encoder.copy_buffer_to_buffer(
                 buffer,
+                staging_buffer.offset,
+                staging,
                 0,
-                &staging_buffer.buffer,
-                0,
-                staging_buffer.buffer.size(),
+                staging.size(),
             );
         }
         Ok(self)
     }
 
+    /// Map the staging buffers in `readbacks` of `frame`, decrementing `pending` as
+    /// each of them is mapped.
     #[inline]
-    fn map_staging_buffers(&mut self) -> &mut Self {
-        for (_, staging_buffer) in self.staging_buffers.iter_mut() {
-            let read_buffer_slice = staging_buffer.buffer.slice(..);
+    fn map_staging_buffers(
+        &mut self,
+        readbacks: &[String],
+        frame: usize,
+        pending: &Arc<AtomicUsize>,
+    ) -> &mut Self {
+        for name in readbacks {
+            let Some(staging_buffer) = self.staging_buffers.get(name) else {
+                continue;
+            };
+            let read_buffer_slice = staging_buffer.frames[frame].0.slice(..);
 
-            read_buffer_slice.map_async(wgpu::MapMode::Read, |result| {
-                if let Some(err) = result.err() {
-                    panic!("{}", err.to_string());
+            let name = name.clone();
+            let map_errors = self.map_errors.clone();
+            let pending = pending.clone();
+            read_buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+                if let Err(err) = result {
+                    map_errors.lock().push((frame, name, err.to_string()));
                 }
+                pending.fetch_sub(1, Ordering::AcqRel);
             });
         }
         self
     }
 
-    /// Read data from `target` staging buffer, return raw bytes
-    #[inline]
-    pub fn try_read_raw<'a>(&'a self, target: &str) -> Result<(impl Deref<Target = [u8]> + 'a)> {
-        let Some(staging_buffer) = &self.staging_buffers.get(target) else {
+    /// Unmap the staging buffers in `readbacks` of `frame`, so that a new run can copy to them.
+    fn unmap_frame(&mut self, readbacks: &[String], frame: usize) {
+        for name in readbacks {
+            let Some(staging_buffer) = self.staging_buffers.get_mut(name) else {
+                continue;
+            };
+
+            let (buffer, mapped) = &mut staging_buffer.frames[frame];
+            if *mapped {
+                buffer.unmap();
+                *mapped = false;
+            }
+
+            if staging_buffer.read_frame == Some(frame) {
+                staging_buffer.read_frame = None;
+            }
+        }
+    }
+
+    /// Staging buffer `target` of the most recently finished run which read it back.
+    fn mapped_staging_buffer(&self, target: &str) -> Result<(&Buffer, &StagingBuffer)> {
+        let Some(staging_buffer) = self.staging_buffers.get(target) else {
             return Err(Error::StagingBufferNotFound(target.to_owned()));
         };
 
-        let result = staging_buffer.buffer.slice(..).get_mapped_range();
+        let Some(frame) = staging_buffer.read_frame else {
+            return Err(Error::StagingBufferNotReady(target.to_owned()));
+        };
 
-        Ok(result)
+        Ok((&staging_buffer.frames[frame].0, staging_buffer))
     }
 
-    /// Read data from `target` staging buffer, return raw bytes
-    /// Panics on error.
+    /// Copy `target` to its staging buffer on the next run, whatever its [`ReadbackPolicy`].
+    ///
+    /// The bytes can be read once the worker is [ready](Self::ready) after that run.
     #[inline]
-    pub fn read_raw<'a>(&'a self, target: &str) -> (impl Deref<Target = [u8]> + 'a) {
-        self.try_read_raw(target).unwrap()
+    pub fn try_request_readback(&mut self, target: &str) -> Result<()> {
+        let Some(staging_buffer) = self.staging_buffers.get_mut(target) else {
+            return Err(Error::StagingBufferNotFound(target.to_owned()));
+        };
+
+        staging_buffer.requested = true;
+
+        Ok(())
     }
 
-    /// Try Read data from `target` staging buffer, return a single `B: Pod`
+    /// Copy `target` to its staging buffer on the next run, whatever its [`ReadbackPolicy`].
+    /// In case of error, this function will panic.
+    #[inline]
+    pub fn request_readback(&mut self, target: &str) {
+        self.try_request_readback(target).unwrap()
+    }
+
+    /// Read data from `target` staging buffer, return raw bytes
+    ///
+    /// Only the bytes of its readback range are returned, if it has one.
+    #[inline]
+    pub fn try_read_raw<'a>(&'a self, target: &str) -> Result<impl Deref<Target = [u8]> + 'a> {
+        let (buffer, _) = self.mapped_staging_buffer(target)?;
+
+        let result = buffer.slice(..).get_mapped_range();
+
+        Ok(result)
+    }
+
+    /// Read data from `target` staging buffer, return raw bytes
+    /// Panics on error.
+    #[inline]
+    pub fn read_raw<'a>(&'a self, target: &str) -> impl Deref<Target = [u8]> + 'a {
+        self.try_read_raw(target).unwrap()
+    }
+
+    /// Try Read data from `target` staging buffer, return a single `B: Pod`
     #[inline]
     pub fn try_read<B: AnyBitPattern>(&self, target: &str) -> Result<B> {
         let result = *from_bytes::<B>(&self.try_read_raw(target)?);
@@ -279,20 +1144,135 @@ impl<W: ComputeWorker> AppComputeWorker<W> {
         self.try_read_vec(target).unwrap()
     }
 
-    /// Write data to `target` buffer.
+    /// Try Read the elements in `range` of `target` staging buffer.
+    ///
+    /// Indices start at the beginning of the buffer read back, even when only a
+    /// range of its bytes is read back.
     #[inline]
-    pub fn try_write<T: NoUninit>(&mut self, target: &str, data: &T) -> Result<()> {
+    pub fn try_read_range<B: AnyBitPattern>(
+        &self,
+        target: &str,
+        range: Range<usize>,
+    ) -> Result<Vec<B>> {
+        let (buffer, staging_buffer) = self.mapped_staging_buffer(target)?;
+        if staging_buffer.texture_layout.is_some() {
+            return Err(Error::BufferNotFound(target.to_owned()));
+        }
+
+        let Range { start, end } = element_bytes::<B>(target, &range)?;
+        let available = staging_buffer.offset..staging_buffer.offset + buffer.size();
+        if start > end || start < available.start || end > available.end {
+            return Err(Error::OutOfBounds {
+                name: target.to_owned(),
+                start,
+                end,
+                available,
+            });
+        }
+
+        let mapped = buffer.slice(..).get_mapped_range();
+        let bytes = &mapped[(start - available.start) as usize..(end - available.start) as usize];
+
+        Ok(bytes
+            .chunks_exact(size_of::<B>())
+            .map(pod_read_unaligned)
+            .collect())
+    }
+
+    /// Try Read the elements in `range` of `target` staging buffer.
+    /// In case of error, this function will panic.
+    #[inline]
+    pub fn read_range<B: AnyBitPattern>(&self, target: &str, range: Range<usize>) -> Vec<B> {
+        self.try_read_range(target, range).unwrap()
+    }
+
+    /// Read data from `target` texture staging buffer, return raw bytes
+    /// without the row padding required by texture copies.
+    #[inline]
+    pub fn try_read_texture_raw(&self, target: &str) -> Result<Vec<u8>> {
+        let (buffer, staging_buffer) = self.mapped_staging_buffer(target)?;
+
+        let Some(layout) = staging_buffer.texture_layout else {
+            return Err(Error::TextureNotFound(target.to_owned()));
+        };
+
+        let mapped = buffer.slice(..).get_mapped_range();
+        let mut bytes = Vec::with_capacity(
+            (layout.bytes_per_row * layout.size.height * layout.size.depth_or_array_layers)
+                as usize,
+        );
+        for row in mapped.chunks_exact(layout.padded_bytes_per_row as usize) {
+            bytes.extend_from_slice(&row[..layout.bytes_per_row as usize]);
+        }
+
+        Ok(bytes)
+    }
+
+    /// Read data from `target` texture staging buffer, return raw bytes
+    /// without the row padding required by texture copies.
+    /// In case of error, this function will panic.
+    #[inline]
+    pub fn read_texture_raw(&self, target: &str) -> Vec<u8> {
+        self.try_read_texture_raw(target).unwrap()
+    }
+
+    /// Try Read data from `target` texture staging buffer, return a vector of `B: Pod`
+    #[inline]
+    pub fn try_read_texture<B: Pod>(&self, target: &str) -> Result<Vec<B>> {
+        let bytes = self.try_read_texture_raw(target)?;
+        let len = bytes.len() / size_of::<B>();
+        let mut result = vec![B::zeroed(); len];
+        cast_slice_mut::<B, u8>(&mut result).copy_from_slice(&bytes[..len * size_of::<B>()]);
+        Ok(result)
+    }
+
+    /// Try Read data from `target` texture staging buffer, return a vector of `B: Pod`
+    /// In case of error, this function will panic.
+    #[inline]
+    pub fn read_texture<B: Pod>(&self, target: &str) -> Vec<B> {
+        self.try_read_texture(target).unwrap()
+    }
+
+    /// Write `bytes` to `target` buffer at `offset`, checking that they fit in it.
+    fn write_bytes(&mut self, target: &str, offset: u64, bytes: &[u8]) -> Result<()> {
         let Some(buffer) = &self.buffers.get(target) else {
             return Err(Error::BufferNotFound(target.to_owned()));
         };
 
-        let bytes = bytes_of(data);
+        let Some(end) = offset.checked_add(bytes.len() as u64) else {
+            return Err(Error::RangeOverflow(target.to_owned()));
+        };
+        if end > buffer.size() {
+            return Err(Error::OutOfBounds {
+                name: target.to_owned(),
+                start: offset,
+                end,
+                available: 0..buffer.size(),
+            });
+        }
 
-        self.render_queue.write_buffer(buffer, 0, bytes);
+        if !offset.is_multiple_of(COPY_BUFFER_ALIGNMENT)
+            || !end.is_multiple_of(COPY_BUFFER_ALIGNMENT)
+        {
+            return Err(Error::UnalignedWrite {
+                name: target.to_owned(),
+                start: offset,
+                end,
+            });
+        }
+
+        self.render_queue.write_buffer(buffer, offset, bytes);
+        self.stats.bytes_uploaded += bytes.len() as u64;
 
         Ok(())
     }
 
+    /// Write data to `target` buffer.
+    #[inline]
+    pub fn try_write<T: NoUninit>(&mut self, target: &str, data: &T) -> Result<()> {
+        self.write_bytes(target, 0, bytes_of(data))
+    }
+
     /// Write data to `target` buffer.
     /// In case of error, this function will panic.
     #[inline]
@@ -303,39 +1283,696 @@ impl<W: ComputeWorker> AppComputeWorker<W> {
     /// Write data to `target` buffer.
     #[inline]
     pub fn try_write_slice<T: NoUninit>(&mut self, target: &str, data: &[T]) -> Result<()> {
-        let Some(buffer) = &self.buffers.get(target) else {
+        self.write_bytes(target, 0, cast_slice(data))
+    }
+
+    /// Write data to `target` buffer.
+    /// In case of error, this function will panic.
+    #[inline]
+    pub fn write_slice<T: NoUninit>(&mut self, target: &str, data: &[T]) {
+        self.try_write_slice(target, data).unwrap()
+    }
+
+    /// Write data to `target` buffer, starting at `offset` bytes.
+    ///
+    /// The written bytes must start and end at multiples of [`wgpu::COPY_BUFFER_ALIGNMENT`].
+    #[inline]
+    pub fn try_write_at<T: NoUninit>(
+        &mut self,
+        target: &str,
+        offset: u64,
+        data: &[T],
+    ) -> Result<()> {
+        if size_of::<T>() == 0 {
+            return Err(Error::ZeroSizedType(target.to_owned()));
+        }
+
+        self.write_bytes(target, offset, cast_slice(data))
+    }
+
+    /// Write data to `target` buffer, starting at `offset` bytes.
+    /// In case of error, this function will panic.
+    #[inline]
+    pub fn write_at<T: NoUninit>(&mut self, target: &str, offset: u64, data: &[T]) {
+        self.try_write_at(target, offset, data).unwrap()
+    }
+
+    /// Write data to the elements in `range` of `target` buffer.
+    ///
+    /// `data` must hold as many elements as `range`, and the written bytes must
+    /// start and end at multiples of [`wgpu::COPY_BUFFER_ALIGNMENT`].
+    #[inline]
+    pub fn try_write_range<T: NoUninit>(
+        &mut self,
+        target: &str,
+        range: Range<usize>,
+        data: &[T],
+    ) -> Result<()> {
+        if range.len() != data.len() {
+            return Err(Error::RangeLengthMismatch {
+                name: target.to_owned(),
+                range: range.len(),
+                data: data.len(),
+            });
+        }
+
+        let bytes = element_bytes::<T>(target, &range)?;
+        self.write_bytes(target, bytes.start, cast_slice(data))
+    }
+
+    /// Write data to the elements in `range` of `target` buffer.
+    /// In case of error, this function will panic.
+    #[inline]
+    pub fn write_range<T: NoUninit>(&mut self, target: &str, range: Range<usize>, data: &[T]) {
+        self.try_write_range(target, range, data).unwrap()
+    }
+
+    /// Write data to `target` buffer, growing it first if `data` doesn't fit.
+    ///
+    /// Growing the buffer clears it, see [`Self::try_resize_buffer`].
+    #[inline]
+    pub fn try_write_vec<T: NoUninit>(&mut self, target: &str, data: &[T]) -> Result<()> {
+        let Some(buffer) = self.buffers.get(target) else {
             return Err(Error::BufferNotFound(target.to_owned()));
         };
 
         let bytes = cast_slice(data);
+        if bytes.len() as u64 > buffer.size() {
+            self.try_resize_buffer(target, bytes.len() as u64, false)?;
+        }
+
+        self.write_bytes(target, 0, bytes)
+    }
+
+    /// Write data to `target` buffer, growing it first if `data` doesn't fit.
+    /// In case of error, this function will panic.
+    #[inline]
+    pub fn write_vec<T: NoUninit>(&mut self, target: &str, data: &[T]) {
+        self.try_write_vec(target, data).unwrap()
+    }
+
+    /// Replace `target` buffer with a new one of `size` bytes, rounded up to
+    /// [`wgpu::COPY_BUFFER_ALIGNMENT`].
+    ///
+    /// With `preserve`, the contents fitting in the new buffer are copied over, it's
+    /// cleared otherwise. Passes bind the new buffer from the next run on. Its staging
+    /// buffer is resized too, unless it only reads back a range which still fits, and
+    /// holds nothing to read until the next readback. Buffers swapped with `target`
+    /// are resized alike, so that swaps keep exchanging buffers of the same size.
+    ///
+    /// Preserving contents copies them on the GPU, which is why uniform and storage
+    /// buffers are all created with [`BufferUsages::COPY_SRC`].
+    pub fn try_resize_buffer(&mut self, target: &str, size: u64, preserve: bool) -> Result<()> {
+        if !self.buffers.contains_key(target) {
+            return Err(Error::BufferNotFound(target.to_owned()));
+        }
+        let size = size.next_multiple_of(COPY_BUFFER_ALIGNMENT);
+
+        // Check every buffer first, so that none of them is resized on error.
+        let swapped = self.swapped_buffers(target);
+        let whole_readbacks = swapped
+            .iter()
+            .map(|name| self.whole_readback(name, size))
+            .collect::<Result<Vec<_>>>()?;
 
-        self.render_queue.write_buffer(buffer, 0, bytes);
+        for (name, whole_readback) in swapped.iter().zip(whole_readbacks) {
+            self.replace_buffer(name, size, preserve, whole_readback);
+        }
 
         Ok(())
     }
 
-    /// Write data to `target` buffer.
+    /// Replace `target` buffer with a new one of `size` bytes.
+    /// In case of error, this function will panic.
+    pub fn resize_buffer(&mut self, target: &str, size: u64, preserve: bool) {
+        self.try_resize_buffer(target, size, preserve).unwrap()
+    }
+
+    /// `target` and every buffer swapped with it, directly or through other swaps.
+    fn swapped_buffers(&self, target: &str) -> Vec<String> {
+        let mut names = vec![target.to_owned()];
+        let mut index = 0;
+        while let Some(name) = names.get(index).cloned() {
+            for step in &self.steps {
+                let StepKind::Swap(a, b) = &step.kind else {
+                    continue;
+                };
+                let other = match (a == &name, b == &name) {
+                    (true, _) => b,
+                    (_, true) => a,
+                    _ => continue,
+                };
+                if self.buffers.contains_key(other) && !names.contains(other) {
+                    names.push(other.clone());
+                }
+            }
+            index += 1;
+        }
+        names
+    }
+
+    /// Whether the staging buffer of `target` reads it back whole, and must be resized
+    /// to `size` bytes along with it. Fails if it reads back a range which doesn't fit.
+    fn whole_readback(&self, target: &str, size: u64) -> Result<bool> {
+        let (Some(buffer), Some(staging_buffer)) =
+            (self.buffers.get(target), self.staging_buffers.get(target))
+        else {
+            return Ok(false);
+        };
+
+        let start = staging_buffer.offset;
+        let end = start + staging_buffer.frames[0].0.size();
+        let whole_readback = start == 0 && end == buffer.size();
+        if !whole_readback && end > size {
+            return Err(Error::OutOfBounds {
+                name: target.to_owned(),
+                start,
+                end,
+                available: 0..size,
+            });
+        }
+
+        Ok(whole_readback)
+    }
+
+    /// Replace `target` buffer with a new one of `size` bytes, and its staging buffer
+    /// too if `whole_readback`.
+    fn replace_buffer(&mut self, target: &str, size: u64, preserve: bool, whole_readback: bool) {
+        let Some(old) = self.buffers.get(target) else {
+            return;
+        };
+        let old_size = old.size();
+
+        let buffer = self.render_device.create_buffer(&BufferDescriptor {
+            label: Some(target),
+            size,
+            usage: old.usage() | BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        if preserve {
+            let mut encoder = self
+                .render_device
+                .create_command_encoder(&CommandEncoderDescriptor { label: None });
+            let copied = old_size.min(size) / COPY_BUFFER_ALIGNMENT * COPY_BUFFER_ALIGNMENT;
+            encoder.copy_buffer_to_buffer(old, 0, &buffer, 0, copied);
+            self.render_queue.submit(Some(encoder.finish()));
+        }
+
+        self.bind_groups
+            .remove_resource(ResourceId::Buffer(old.id()));
+        self.buffers.insert(target.to_owned(), buffer);
+
+        if whole_readback {
+            self.resize_staging_buffer(target, size);
+        }
+    }
+
+    /// Replace every frame of the staging buffer `target` with buffers of `size` bytes.
+    fn resize_staging_buffer(&mut self, target: &str, size: u64) {
+        let Some(staging_buffer) = self.staging_buffers.get_mut(target) else {
+            return;
+        };
+
+        let frames = staging_buffer
+            .frames
+            .iter()
+            .map(|_| {
+                let buffer = self.render_device.create_buffer(&BufferDescriptor {
+                    label: Some(target),
+                    size,
+                    usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                });
+                (buffer, false)
+            })
+            .collect();
+        let old_frames = std::mem::replace(&mut staging_buffer.frames, frames);
+        staging_buffer.read_frame = None;
+
+        self.retire_staging_frames(target, &old_frames);
+    }
+
+    /// Keep the previous `frames` of the staging buffer `target` alive until the runs
+    /// in flight copying to them finish, and stop reading them back.
+    fn retire_staging_frames(&mut self, target: &str, frames: &[(Buffer, bool)]) {
+        for submission in &mut self.submissions {
+            if let Some(index) = submission.readbacks.iter().position(|name| name == target) {
+                submission.readbacks.remove(index);
+                submission.retired.push(frames[submission.frame].0.clone());
+            }
+        }
+    }
+
+    /// Write tightly packed texel data to `target` texture.
+    #[inline]
+    pub fn try_write_texture<T: NoUninit>(&mut self, target: &str, data: &[T]) -> Result<()> {
+        let Some(texture) = &self.textures.get(target) else {
+            return Err(Error::TextureNotFound(target.to_owned()));
+        };
+
+        let size = texture.texture.size();
+        let block_size = texture
+            .texture
+            .format()
+            .block_copy_size(None)
+            .unwrap_or_default();
+
+        let bytes = cast_slice(data);
+        self.render_queue.write_texture(
+            texture.texture.as_image_copy(),
+            bytes,
+            TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(size.width * block_size),
+                rows_per_image: Some(size.height),
+            },
+            size,
+        );
+        self.stats.bytes_uploaded += bytes.len() as u64;
+
+        Ok(())
+    }
+
+    /// Write tightly packed texel data to `target` texture.
     /// In case of error, this function will panic.
     #[inline]
-    pub fn write_slice<T: NoUninit>(&mut self, target: &str, data: &[T]) {
-        self.try_write_slice(target, data).unwrap()
+    pub fn write_texture<T: NoUninit>(&mut self, target: &str, data: &[T]) {
+        self.try_write_texture(target, data).unwrap()
+    }
+
+    /// Set the push constants of a compute pass, from the next run on.
+    ///
+    /// They must exactly fill the ranges declared by [`ComputeShader::push_constant_ranges`]
+    /// for compute shaders.
+    #[inline]
+    pub fn try_set_push_constants<T: NoUninit>(
+        &mut self,
+        step: impl Into<StepId>,
+        data: &T,
+    ) -> Result<()> {
+        if !self
+            .render_device
+            .features()
+            .contains(Features::PUSH_CONSTANTS)
+        {
+            return Err(Error::PushConstantsUnsupported);
+        }
+
+        let index = self.step_index(&step.into())?;
+        let id = self.steps[index].id(index);
+
+        let StepKind::ComputePass(compute_pass) = &mut self.steps[index].kind else {
+            return Err(Error::InvalidStep(format!("{:?}", self.steps[index])));
+        };
+
+        let data = bytes_of(data);
+        if !compute_pass.fits_push_constants(data.len()) {
+            return Err(Error::PushConstantsMismatch {
+                step: id.to_string(),
+                size: data.len(),
+                range: compute_pass.push_constant_range.clone().unwrap_or_default(),
+            });
+        }
+
+        compute_pass.push_constants = Some(data.to_vec());
+
+        Ok(())
+    }
+
+    /// Set the push constants of a compute pass, from the next run on.
+    /// In case of error, this function will panic.
+    #[inline]
+    pub fn set_push_constants<T: NoUninit>(&mut self, step: impl Into<StepId>, data: &T) {
+        self.try_set_push_constants(step, data).unwrap()
+    }
+
+    /// Index of the step identified by `step`.
+    fn step_index(&self, step: &StepId) -> Result<usize> {
+        match step {
+            StepId::Index(index) if *index < self.steps.len() => Ok(*index),
+            StepId::Label(label) => self
+                .steps
+                .iter()
+                .position(|step| step.label.as_ref() == Some(label))
+                .ok_or_else(|| Error::StepNotFound(step.to_string())),
+            StepId::Index(_) => Err(Error::StepNotFound(step.to_string())),
+        }
+    }
+
+    /// Set the workgroup counts dispatched by a compute pass, from the next run on.
+    ///
+    /// This replaces any indirect or dynamic dispatch the pass was created with.
+    #[inline]
+    pub fn try_set_workgroups(
+        &mut self,
+        step: impl Into<StepId>,
+        workgroups: [u32; 3],
+    ) -> Result<()> {
+        let index = self.step_index(&step.into())?;
+
+        let StepKind::ComputePass(compute_pass) = &mut self.steps[index].kind else {
+            return Err(Error::InvalidStep(format!("{:?}", self.steps[index])));
+        };
+
+        compute_pass.dispatch = Dispatch::Direct(workgroups);
+
+        Ok(())
+    }
+
+    /// Set the workgroup counts dispatched by a compute pass, from the next run on.
+    /// In case of error, this function will panic.
+    #[inline]
+    pub fn set_workgroups(&mut self, step: impl Into<StepId>, workgroups: [u32; 3]) {
+        self.try_set_workgroups(step, workgroups).unwrap()
+    }
+
+    /// Whether the worker has a buffer, texture, sampler or image called `name`.
+    fn has_resource(&self, name: &str) -> bool {
+        self.buffers.contains_key(name)
+            || self.textures.contains_key(name)
+            || self.samplers.contains_key(name)
+            || self.images.contains_key(name)
+    }
+
+    /// Add a new buffer of `kind` to the worker, and fill it with `data`.
+    ///
+    /// It gets the usages set with [`AppComputeWorkerBuilder::set_extra_buffer_usages`]
+    /// too. Steps can use it once it's added, see [`Self::try_insert_pass`].
+    pub fn try_add_buffer<T: ShaderType + WriteInto>(
+        &mut self,
+        name: &str,
+        kind: BufferKind,
+        data: &T,
+    ) -> Result<()> {
+        if self.has_resource(name) {
+            return Err(Error::DuplicateName(name.to_owned()));
+        }
+
+        let (contents, usage) = match kind {
+            BufferKind::Uniform => {
+                T::assert_uniform_compat();
+                let mut buffer = UniformBuffer::new(Vec::new());
+                buffer.write(data).map_err(|error| Error::Encoding {
+                    name: name.to_owned(),
+                    message: error.to_string(),
+                })?;
+                (buffer.into_inner(), BufferUsages::UNIFORM)
+            }
+            BufferKind::Storage { .. } => {
+                let mut buffer = StorageBuffer::new(Vec::new());
+                buffer.write(data).map_err(|error| Error::Encoding {
+                    name: name.to_owned(),
+                    message: error.to_string(),
+                })?;
+                (buffer.into_inner(), BufferUsages::STORAGE)
+            }
+        };
+
+        let mut usage = usage | BufferUsages::COPY_DST | BufferUsages::COPY_SRC;
+        if let Some(extra_usages) = self.extra_buffer_usages {
+            usage |= extra_usages;
+        }
+
+        let buffer = self
+            .render_device
+            .create_buffer_with_data(&BufferInitDescriptor {
+                label: Some(name),
+                contents: &contents,
+                usage,
+            });
+
+        self.buffers.insert(name.to_owned(), buffer);
+        self.buffer_kinds.insert(name.to_owned(), kind);
+
+        Ok(())
+    }
+
+    /// Add a new buffer of `kind` to the worker, and fill it with `data`.
+    /// In case of error, this function will panic.
+    pub fn add_buffer<T: ShaderType + WriteInto>(
+        &mut self,
+        name: &str,
+        kind: BufferKind,
+        data: &T,
+    ) {
+        self.try_add_buffer(name, kind, data).unwrap()
+    }
+
+    /// Add a new read/write storage buffer to the worker, along with a staging
+    /// buffer to read it back, like [`AppComputeWorkerBuilder::add_staging`].
+    pub fn try_add_staging<T: ShaderType + WriteInto>(
+        &mut self,
+        name: &str,
+        data: &T,
+    ) -> Result<()> {
+        self.try_add_buffer(name, BufferKind::Storage { read_only: false }, data)?;
+
+        let mut staging_buffer = StagingBuffer::new(
+            self.render_device.create_buffer(&BufferDescriptor {
+                label: Some(name),
+                size: self.buffers[name].size(),
+                usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+                mapped_at_creation: true,
+            }),
+            None,
+        );
+        staging_buffer.allocate_frames(&self.render_device, name, self.frames_in_flight);
+        self.staging_buffers.insert(name.to_owned(), staging_buffer);
+
+        Ok(())
+    }
+
+    /// Add a new read/write storage buffer to the worker, along with a staging
+    /// buffer to read it back.
+    /// In case of error, this function will panic.
+    pub fn add_staging<T: ShaderType + WriteInto>(&mut self, name: &str, data: &T) {
+        self.try_add_staging(name, data).unwrap()
+    }
+
+    /// Remove `target` buffer from the worker, along with its staging buffer.
+    ///
+    /// Fails if a step still binds, swaps or dispatches from it, disabled steps included.
+    pub fn try_remove_buffer(&mut self, target: &str) -> Result<()> {
+        if !self.buffers.contains_key(target) {
+            return Err(Error::BufferNotFound(target.to_owned()));
+        }
+
+        if let Some((index, step)) = self
+            .steps
+            .iter()
+            .enumerate()
+            .find(|(_, step)| step.uses(target))
+        {
+            return Err(Error::ResourceInUse {
+                name: target.to_owned(),
+                step: step.id(index).to_string(),
+            });
+        }
+
+        if let Some(buffer) = self.buffers.remove(target) {
+            self.bind_groups
+                .remove_resource(ResourceId::Buffer(buffer.id()));
+        }
+        self.buffer_kinds.remove(target);
+
+        if let Some(staging_buffer) = self.staging_buffers.remove(target) {
+            self.retire_staging_frames(target, &staging_buffer.frames);
+        }
+
+        Ok(())
+    }
+
+    /// Remove `target` buffer from the worker, along with its staging buffer.
+    /// In case of error, this function will panic.
+    pub fn remove_buffer(&mut self, target: &str) {
+        self.try_remove_buffer(target).unwrap()
     }
 
-    fn submit(&mut self) -> &mut Self {
+    /// Insert a compute pass using the shader `S` before the step at `index`, binding
+    /// `vars` to the group 0 in order, like [`AppComputeWorkerBuilder::add_pass`].
+    ///
+    /// Steps from `index` on move one index up. If no other pass uses the pipeline of
+    /// `S`, the worker waits for it to be created before running again.
+    pub fn try_insert_pass<S: ComputeShader>(
+        &mut self,
+        index: usize,
+        workgroups: [u32; 3],
+        vars: &[&str],
+    ) -> Result<()> {
+        if index > self.steps.len() {
+            return Err(Error::StepNotFound(StepId::Index(index).to_string()));
+        }
+
+        if let Some(name) = vars.iter().find(|name| !self.has_resource(name)) {
+            return Err(Error::UnknownResource {
+                step: StepId::Index(index).to_string(),
+                name: name.to_string(),
+            });
+        }
+
+        let constants = pass_constants::<S>(&[]);
+        let pipeline_key = pipeline_key(S::type_path(), &constants);
+
+        if !self.pipelines.contains_key(&pipeline_key) {
+            let Some(descriptor) = pipeline_descriptor::<S>(&self.asset_server, &constants) else {
+                return Err(Error::MissingShader(S::type_path().to_owned()));
+            };
+
+            self.queued_pipelines
+                .push((pipeline_key.clone(), descriptor, constants));
+            self.pipelines.insert(pipeline_key.clone(), None);
+        }
+
+        self.steps.insert(
+            index,
+            Step {
+                label: None,
+                enabled: true,
+                kind: StepKind::ComputePass(ComputePass {
+                    dispatch: Dispatch::Direct(workgroups),
+                    bindings: vars_bindings(vars),
+                    bind_by_name: false,
+                    shader_type_path: S::type_path().to_owned(),
+                    pipeline_key,
+                    push_constant_range: ComputePass::push_constant_range::<S>(),
+                    push_constants: None,
+                }),
+            },
+        );
+
+        // Bind groups are cached by step index.
+        self.bind_groups.clear();
+
+        Ok(())
+    }
+
+    /// Insert a compute pass using the shader `S` before the step at `index`.
+    /// In case of error, this function will panic.
+    pub fn insert_pass<S: ComputeShader>(
+        &mut self,
+        index: usize,
+        workgroups: [u32; 3],
+        vars: &[&str],
+    ) {
+        self.try_insert_pass::<S>(index, workgroups, vars).unwrap()
+    }
+
+    /// Remove a step from the worker. Steps after it move one index down.
+    pub fn try_remove_step(&mut self, step: impl Into<StepId>) -> Result<()> {
+        let index = self.step_index(&step.into())?;
+        self.steps.remove(index);
+
+        // Bind groups are cached by step index.
+        self.bind_groups.clear();
+
+        Ok(())
+    }
+
+    /// Remove a step from the worker.
+    /// In case of error, this function will panic.
+    pub fn remove_step(&mut self, step: impl Into<StepId>) {
+        self.try_remove_step(step).unwrap()
+    }
+
+    /// Label a step, so that it can be referred to by its label instead of its index,
+    /// like [`AppComputeWorkerBuilder::label`]. The label also names the step in GPU captures.
+    pub fn try_label_step(&mut self, step: impl Into<StepId>, label: &str) -> Result<()> {
+        let index = self.step_index(&step.into())?;
+        if self
+            .steps
+            .iter()
+            .enumerate()
+            .any(|(i, step)| i != index && step.label.as_deref() == Some(label))
+        {
+            return Err(Error::DuplicateLabel(label.to_owned()));
+        }
+
+        self.steps[index].label = Some(label.to_owned());
+
+        Ok(())
+    }
+
+    /// Label a step, so that it can be referred to by its label instead of its index.
+    /// In case of error, this function will panic.
+    pub fn label_step(&mut self, step: impl Into<StepId>, label: &str) {
+        self.try_label_step(step, label).unwrap()
+    }
+
+    /// Enable or disable a step. Runs skip disabled steps, which keep their index.
+    pub fn try_set_step_enabled(&mut self, step: impl Into<StepId>, enabled: bool) -> Result<()> {
+        let index = self.step_index(&step.into())?;
+        self.steps[index].enabled = enabled;
+
+        Ok(())
+    }
+
+    /// Enable or disable a step.
+    /// In case of error, this function will panic.
+    pub fn set_step_enabled(&mut self, step: impl Into<StepId>, enabled: bool) {
+        self.try_set_step_enabled(step, enabled).unwrap()
+    }
+
+    /// Submit the recorded run, along with the current job, and map the staging
+    /// buffers in `readbacks`.
+    fn submit(&mut self, readbacks: Vec<String>) -> &mut Self {
+        let frame = self.next_frame;
+        self.next_frame = (frame + 1) % self.frames_in_flight;
+        self.runs += 1;
+        self.unmap_frame(&readbacks, frame);
+
         let encoder = self.command_encoder.take().unwrap();
         self.render_queue.submit(Some(encoder.finish()));
-        self.state = WorkerState::Working {
+
+        for name in &readbacks {
+            if let Some(staging_buffer) = self.staging_buffers.get_mut(name) {
+                staging_buffer.requested = false;
+            }
+        }
+
+        let pending = Arc::new(AtomicUsize::new(
+            readbacks.len() + 1 + usize::from(self.timestamps.is_some()),
+        ));
+        self.map_staging_buffers(&readbacks, frame, &pending);
+        let timestamps = self
+            .timestamps
+            .as_mut()
+            .map(|timestamps| timestamps.map(frame, &pending));
+        let done = pending.clone();
+        let end_time = Arc::new(OnceLock::new());
+        let completed = end_time.clone();
+        self.render_queue.on_submitted_work_done(move || {
+            let _ = completed.set(SystemTime::now());
+            done.fetch_sub(1, Ordering::AcqRel);
+        });
+
+        self.submissions.push_back(Submission {
+            frame,
             start_time: SystemTime::now(),
+            end_time,
+            pending,
+            readbacks,
+            retired: vec![],
+            timestamps,
+            job: self.current_job.take(),
+        });
+
+        match self.run_mode {
+            RunMode::Continuous => {}
+            RunMode::OneShot(_) => self.run_mode = RunMode::OneShot(false),
         };
+
         self
     }
 
+    /// Check whether the oldest submitted run finished, setting `fell_back` if it had
+    /// to be waited for after running asynchronously for too long.
     #[inline]
-    fn poll(&mut self) -> bool {
-        let WorkerState::Working { start_time } = self.state else {
-            // We will always be in this state at this point
-            panic!("Called AppComputeWorker::poll() without being in the Working state!");
+    fn poll(&mut self, fell_back: &mut bool) -> bool {
+        let Some(submission) = self.submissions.front() else {
+            return false;
         };
+        let start_time = submission.start_time;
 
         let is_async = self
             .maximum_async_time
@@ -347,35 +1984,35 @@ impl<W: ComputeWorker> AppComputeWorker<W> {
             })
             .unwrap_or(true);
 
-        if is_async {
-            match self
-                .render_device
-                .wgpu_device()
-                .poll(wgpu::MaintainBase::Poll)
-            {
-                // The first few times the poll occurs the queue will be empty, because wgpu hasn't started anything yet.
-                // We need to wait until `MaintainResult::Ok`, which means wgpu has started to process our data.
-                // Then, the next time the queue is empty (`MaintainResult::SubmissionQueueEmpty`), wgpu has finished processing the data and we are done.
-                wgpu::MaintainResult::SubmissionQueueEmpty => {
-                    let res = self.submission_queue_processed;
-                    self.submission_queue_processed = false;
-                    res
-                }
-                wgpu::MaintainResult::Ok => {
-                    self.submission_queue_processed = true;
-                    false
-                }
-            }
+        let maintain = if is_async {
+            wgpu::MaintainBase::Poll
         } else {
-            match self
-                .render_device
-                .wgpu_device()
-                .poll(wgpu::MaintainBase::Wait)
+            // Synchronous workers always wait, only count workers giving up on running asynchronously.
+            if !*fell_back
+                && self
+                    .maximum_async_time
+                    .is_some_and(|maximum| !maximum.is_zero())
             {
-                wgpu::MaintainResult::SubmissionQueueEmpty => true,
-                wgpu::MaintainResult::Ok => false,
+                self.stats.sync_fallbacks += 1;
+                *fell_back = true;
             }
-        }
+            wgpu::MaintainBase::Wait
+        };
+        self.render_device.wgpu_device().poll(maintain);
+
+        // The callbacks counting down `pending` run while polling.
+        self.submissions
+            .front()
+            .is_some_and(|submission| submission.pending.load(Ordering::Acquire) == 0)
+    }
+
+    /// Timings of the most recently finished run, if the worker was built with
+    /// [`AppComputeWorkerBuilder::profile`].
+    ///
+    /// Passes are only timed on devices supporting [`Features::TIMESTAMP_QUERY`],
+    /// otherwise only the total duration of the run is measured.
+    pub fn timings(&self) -> Option<&RunTimings> {
+        self.timings.as_ref()
     }
 
     /// Check if the worker is ready to be read from.
@@ -384,9 +2021,80 @@ impl<W: ComputeWorker> AppComputeWorker<W> {
         self.state == WorkerState::FinishedWorking
     }
 
+    /// Queue a run of the worker with its own inputs, resolving to the contents of
+    /// its staging buffers once it ran.
+    ///
+    /// Jobs run one after the other, each writing its inputs to the buffers of the
+    /// worker right before being submitted, and resolve to their own copy of the staging
+    /// buffers. Inputs aren't reset after a job ran, so jobs should write every buffer
+    /// their results depend on. On one-shot workers, starting a job executes the worker
+    /// like [`Self::execute`].
+    ///
+    /// If the worker fails, the running job and every queued one fail with
+    /// [`Error::JobFailed`].
+    pub fn submit_job(&mut self, job: ComputeJob) -> ComputeJobHandle {
+        let (job, handle) = PendingJob::new(job);
+        self.jobs.push_back(job);
+        handle
+    }
+
+    /// Write the inputs of the next queued job, unless a job is waiting to be submitted.
+    ///
+    /// Jobs whose inputs can't be written fail right away.
+    fn start_next_job(&mut self) {
+        while self.current_job.is_none() {
+            let Some(job) = self.jobs.pop_front() else {
+                return;
+            };
+
+            let written = job
+                .job
+                .writes
+                .iter()
+                .try_for_each(|(target, data)| self.try_write_slice(target, data));
+
+            match written {
+                Ok(()) => {
+                    self.execute();
+                    self.current_job = Some(job);
+                }
+                Err(error) => job.resolve(Err(error)),
+            }
+        }
+    }
+
+    /// Copy the contents of every staging buffer, once the worker finished running.
+    fn job_output(&self) -> ComputeJobOutput {
+        let staging_buffers = self
+            .staging_buffers
+            .iter()
+            .filter_map(|(name, staging_buffer)| {
+                let bytes = match staging_buffer.texture_layout {
+                    Some(_) => self.try_read_texture_raw(name).ok()?,
+                    None => self.try_read_raw(name).ok()?.to_vec(),
+                };
+                Some((name.clone(), bytes))
+            })
+            .collect();
+
+        ComputeJobOutput { staging_buffers }
+    }
+
+    /// Current state of the worker.
+    #[inline]
+    pub fn state(&self) -> &WorkerState {
+        &self.state
+    }
+
     /// Tell the worker to execute the compute shader at the end of the current frame
+    ///
+    /// This also makes a [failed](WorkerState::Failed) worker run again.
     #[inline]
     pub fn execute(&mut self) {
+        if self.state == WorkerState::Failed {
+            self.state = WorkerState::Available;
+        }
+
         match self.run_mode {
             RunMode::Continuous => {}
             RunMode::OneShot(_) => self.run_mode = RunMode::OneShot(true),
@@ -395,85 +2103,538 @@ impl<W: ComputeWorker> AppComputeWorker<W> {
 
     #[inline]
     fn ready_to_execute(&self) -> bool {
-        (!matches!(self.state, WorkerState::Working { start_time: _ }))
-            && (self.run_mode != RunMode::OneShot(false))
+        self.state != WorkerState::Failed
+            && self.submissions.len() < self.frames_in_flight
+            && self.run_mode != RunMode::OneShot(false)
     }
 
-    pub(crate) fn run(mut worker: ResMut<Self>) {
-        if worker.ready() {
-            worker.state = WorkerState::Available;
+    /// Record every step of the worker, and the copies to the staging buffers of
+    /// the next frame in flight. Returns the staging buffers copied to.
+    fn record(&mut self) -> Result<Vec<String>> {
+        self.sync_images()?;
+
+        self.command_encoder = Some(self.render_device.create_command_encoder(
+            &CommandEncoderDescriptor {
+                label: Some(type_name::<W>()),
+            },
+        ));
+
+        if let Some(timestamps) = &mut self.timestamps {
+            let passes = self
+                .steps
+                .iter()
+                .filter(|step| step.enabled && matches!(step.kind, StepKind::ComputePass(_)))
+                .count();
+            timestamps.reserve(&self.render_device, &self.render_queue, passes);
         }
 
+        // Workaround for interior mutability
+        for i in 0..self.steps.len() {
+            if !self.steps[i].enabled {
+                continue;
+            }
+
+            match self.steps[i].kind {
+                StepKind::ComputePass(_) => self.dispatch(i)?,
+                StepKind::Swap(_, _) => self.swap(i)?,
+            };
+        }
+
+        if let (Some(timestamps), Some(encoder)) = (&self.timestamps, &mut self.command_encoder) {
+            timestamps.resolve(encoder, self.next_frame);
+        }
+
+        let readbacks = self.due_readbacks();
+        if let Some(encoder) = &mut self.command_encoder {
+            encoder.push_debug_group("readback");
+        }
+        self.read_staging_buffers(&readbacks)?;
+        if let Some(encoder) = &mut self.command_encoder {
+            encoder.pop_debug_group();
+        }
+        Ok(readbacks)
+    }
+
+    /// Stop running the worker after `error`, or panic in strict mode.
+    fn fail(&mut self, error: Error, errors: &mut EventWriter<ComputeWorkerError<W>>) {
+        if self.strict {
+            panic!("{:?}", error);
+        }
+
+        // Runs in flight are still finished once the worker resumes, only their jobs fail now.
+        let submitted = self
+            .submissions
+            .iter_mut()
+            .filter_map(|submission| submission.job.take());
+        let jobs = submitted
+            .chain(self.current_job.take())
+            .chain(self.jobs.drain(..));
+        for job in jobs {
+            job.resolve(Err(Error::JobFailed(error.to_string())));
+        }
+
+        // Drop the passes recorded before the error.
+        self.command_encoder = None;
+        self.state = WorkerState::Failed;
+        errors.write(ComputeWorkerError::new(error));
+    }
+
+    pub(crate) fn run(
+        mut worker: ResMut<Self>,
+        mut errors: EventWriter<ComputeWorkerError<W>>,
+        mut finished: EventWriter<ComputeWorkerFinished<W>>,
+        frame_count: Option<Res<FrameCount>>,
+    ) {
+        if worker.state == WorkerState::Failed {
+            return;
+        }
+
+        worker.start_next_job();
+
         if worker.ready_to_execute() {
-            // Workaround for interior mutability
-            for i in 0..worker.steps.len() {
-                let result = match worker.steps[i] {
-                    Step::ComputePass(_) => worker.dispatch(i),
-                    Step::Swap(_, _) => worker.swap(i),
-                };
+            let encode_start = Instant::now();
+            match worker.record() {
+                Ok(readbacks) => {
+                    worker.stats.encode_time = Some(encode_start.elapsed());
+                    worker.submit(readbacks);
+                }
+                // Try again next frame, still picking up the runs in flight meanwhile.
+                Err(Error::PipelineNotReady | Error::ImageNotReady(_)) => {}
+                Err(error) => {
+                    worker.fail(error, &mut errors);
+                    return;
+                }
+            }
+        }
 
-                if let Err(err) = result {
-                    match err {
-                        Error::PipelineNotReady => return,
-                        _ => panic!("{:?}", err),
-                    }
+        let frame = frame_count.map_or(0, |frame_count| frame_count.0);
+        let mut finished_any = false;
+        let mut fell_back = false;
+        while worker.poll(&mut fell_back) {
+            let Some(submission) = worker.submissions.pop_front() else {
+                break;
+            };
+            if let Err(error) = worker.finish(submission, frame, &mut finished) {
+                worker.fail(error, &mut errors);
+                return;
+            }
+            finished_any = true;
+        }
+
+        if finished_any {
+            worker.state = WorkerState::FinishedWorking;
+        } else if let Some(submission) = worker.submissions.front() {
+            worker.state = WorkerState::Working {
+                start_time: submission.start_time,
+            };
+        } else if worker.ready() || matches!(worker.state, WorkerState::Working { .. }) {
+            worker.state = WorkerState::Available;
+        }
+    }
+
+    /// Make the results of the finished `submission` readable, and hand them to its job.
+    fn finish(
+        &mut self,
+        submission: Submission,
+        frame: u32,
+        finished: &mut EventWriter<ComputeWorkerFinished<W>>,
+    ) -> Result<()> {
+        // Unmap the timestamps first, the next run of the frame copies to them.
+        let passes = submission
+            .timestamps
+            .map(PendingTimestamps::read)
+            .unwrap_or_default();
+        let end_time = submission
+            .end_time
+            .get()
+            .copied()
+            .unwrap_or_else(SystemTime::now);
+        let total = end_time
+            .duration_since(submission.start_time)
+            .unwrap_or_default();
+
+        let gpu_time = (!passes.is_empty()).then(|| {
+            passes
+                .iter()
+                .map(|(_, duration)| *duration)
+                .sum::<Duration>()
+        });
+        if gpu_time.is_some() {
+            self.stats.gpu_time = gpu_time;
+        }
+        self.stats.submit_to_complete = Some(total);
+        let duration = gpu_time.unwrap_or(total);
+        if self.profile {
+            self.timings = Some(RunTimings { passes, total });
+        }
+
+        let mut map_errors = self.map_errors.lock();
+        let (failed, others) = std::mem::take(&mut *map_errors)
+            .into_iter()
+            .partition::<Vec<_>, _>(|(failed, _, _)| *failed == submission.frame);
+        *map_errors = others;
+        drop(map_errors);
+
+        for name in &submission.readbacks {
+            let Some(staging_buffer) = self.staging_buffers.get_mut(name) else {
+                continue;
+            };
+
+            // By this the staging buffers would've been mapped.
+            let mapped = !failed.iter().any(|(_, failed, _)| failed == name);
+            staging_buffer.frames[submission.frame].1 = mapped;
+            if mapped {
+                staging_buffer.read_frame = Some(submission.frame);
+                self.stats.bytes_read_back += staging_buffer.frames[submission.frame].0.size();
+            }
+        }
+
+        if let Some((_, name, message)) = failed
+            .into_iter()
+            .find(|(_, name, _)| submission.readbacks.contains(name))
+        {
+            let error = Error::StagingBufferMapFailed { name, message };
+            if let Some(job) = submission.job {
+                job.resolve(Err(Error::JobFailed(error.to_string())));
+            }
+            return Err(error);
+        }
+
+        finished.write(ComputeWorkerFinished::new(frame, duration));
+
+        if let Some(job) = submission.job {
+            job.resolve(Ok(self.job_output()));
+        }
+
+        Ok(())
+    }
+
+    /// Evaluate the workgroup counts of dynamic passes before the worker runs.
+    pub(crate) fn update_workgroups(world: &mut World) {
+        world.resource_scope(|world, mut worker: Mut<Self>| {
+            if !worker.ready_to_execute() {
+                return;
+            }
+
+            for step in &mut worker.steps {
+                if let StepKind::ComputePass(ComputePass {
+                    dispatch: Dispatch::Dynamic { workgroups, update },
+                    ..
+                }) = &mut step.kind
+                {
+                    *workgroups = (update.0)(world);
                 }
             }
+        });
+    }
 
-            worker.read_staging_buffers().unwrap();
-            worker.submit();
-            worker.map_staging_buffers();
+    /// Pick up the pipelines of the worker once they're created, and again whenever
+    /// they're recompiled after their shader changed.
+    pub(crate) fn extract_pipelines(
+        mut worker: ResMut<Self>,
+        pipeline_cache: Res<PipelineCache>,
+        mut reloads: EventWriter<ComputePipelineReloaded<W>>,
+    ) {
+        for (pipeline_key, descriptor, constants) in std::mem::take(&mut worker.queued_pipelines) {
+            let cached_id = pipeline_cache.queue_compute_pipeline(descriptor, constants);
+            worker.cached_pipeline_ids.insert(pipeline_key, cached_id);
         }
 
-        if worker.run_mode != RunMode::OneShot(false) && worker.poll() {
-            for (_, staging_buffer) in worker.staging_buffers.iter_mut() {
-                // By this the staging buffers would've been mapped.
-                staging_buffer.mapped = true;
+        for (pipeline_key, cached_id) in &worker.cached_pipeline_ids.clone() {
+            let Some(current) = worker.pipelines.get(pipeline_key) else {
+                continue;
+            };
+            let current_id = current.as_ref().map(|current| current.id());
+
+            let cached_id = *cached_id;
+
+            // Recompiling pipelines aren't available, keep using the previous version meanwhile.
+            let Some(pipeline) = pipeline_cache.get_compute_pipeline(cached_id) else {
+                worker.update_shader_error(pipeline_key, cached_id, &pipeline_cache);
+                continue;
+            };
+
+            if current_id == Some(pipeline.id()) {
+                continue;
             }
 
-            worker.state = WorkerState::FinishedWorking;
-            worker.command_encoder = Some(
+            let shader = worker.pipeline_shader(pipeline_key);
+            let fixed = worker.shader_errors.remove(pipeline_key).is_some();
+            let reloaded = current_id.is_some() || fixed;
+
+            worker
+                .pipelines
+                .insert(pipeline_key.clone(), Some(pipeline.clone()));
+
+            if let Some(shader_bindings) = pipeline_cache.get_compute_pipeline_bindings(cached_id) {
+                worker.bind_by_name(pipeline_key, &shader_bindings);
                 worker
-                    .render_device
-                    .create_command_encoder(&CommandEncoderDescriptor { label: None }),
-            );
+                    .shader_bindings
+                    .insert(pipeline_key.clone(), shader_bindings);
+            }
 
-            match worker.run_mode {
-                RunMode::Continuous => {}
-                RunMode::OneShot(_) => worker.run_mode = RunMode::OneShot(false),
+            if reloaded {
+                worker.reload(pipeline_key);
+                if let Some(shader) = shader {
+                    reloads.write(ComputePipelineReloaded::new(shader));
+                }
+            }
+        }
+
+        worker.report_unmatched_names();
+        worker.resolve_element_workgroups(&pipeline_cache);
+    }
+
+    /// List the variables of passes bound by name which no resource matches in
+    /// [`Self::shader_errors`], once their shader is processed.
+    ///
+    /// Checked every frame since resources can be added and removed at runtime.
+    fn report_unmatched_names(&mut self) {
+        for step in &self.steps {
+            let StepKind::ComputePass(compute_pass) = &step.kind else {
+                continue;
             };
+
+            if !compute_pass.bind_by_name
+                || !self
+                    .shader_bindings
+                    .contains_key(&compute_pass.pipeline_key)
+            {
+                continue;
+            }
+
+            let unmatched = compute_pass
+                .bindings
+                .iter()
+                .filter(|binding| self.resource_id(&binding.name).is_none())
+                .map(|binding| format!("`{}`", binding.name))
+                .collect::<Vec<_>>();
+
+            if unmatched.is_empty() {
+                self.shader_errors.remove(&compute_pass.pipeline_key);
+            } else {
+                let message = format!(
+                    "The worker has no buffer, texture or sampler named {}.",
+                    unmatched.join(", ")
+                );
+                self.shader_errors.insert(
+                    compute_pass.pipeline_key.clone(),
+                    (compute_pass.shader_type_path.clone(), message),
+                );
+            }
         }
     }
 
-    pub(crate) fn unmap_all(mut worker: ResMut<Self>) {
-        for (_, staging_buffer) in &mut worker.staging_buffers {
-            if staging_buffer.mapped {
-                staging_buffer.buffer.unmap();
-                staging_buffer.mapped = false;
+    /// Forget about everything derived from the previous version of the pipeline
+    /// `pipeline_key`, and resume the worker if it failed.
+    fn reload(&mut self, pipeline_key: &str) {
+        for step in &mut self.steps {
+            if let StepKind::ComputePass(ComputePass {
+                dispatch: Dispatch::Elements { workgroups, .. },
+                pipeline_key: key,
+                ..
+            }) = &mut step.kind
+                && key == pipeline_key
+            {
+                *workgroups = None;
             }
         }
+
+        self.bind_groups.clear();
+
+        if self.state == WorkerState::Failed {
+            self.state = WorkerState::Available;
+        }
+    }
+
+    /// Type path of the shader of the pipeline `pipeline_key`.
+    fn pipeline_shader(&self, pipeline_key: &str) -> Option<String> {
+        self.steps.iter().find_map(|step| match &step.kind {
+            StepKind::ComputePass(compute_pass) if compute_pass.pipeline_key == pipeline_key => {
+                Some(compute_pass.shader_type_path.clone())
+            }
+            _ => None,
+        })
+    }
+
+    /// Record why the pipeline `pipeline_key` couldn't be created, or forget about
+    /// it once it's being created again.
+    fn update_shader_error(
+        &mut self,
+        pipeline_key: &str,
+        cached_id: AppCachedComputePipelineId,
+        pipeline_cache: &PipelineCache,
+    ) {
+        let Some(shader) = self.pipeline_shader(pipeline_key) else {
+            return;
+        };
+
+        if !matches!(
+            pipeline_cache.get_compute_pipeline_state(cached_id),
+            CachedPipelineState::Err(_)
+        ) {
+            self.shader_errors.remove(pipeline_key);
+            return;
+        }
+
+        if self.shader_errors.contains_key(pipeline_key) {
+            return;
+        }
+
+        if let Some(message) = pipeline_cache.get_compute_pipeline_error(cached_id) {
+            self.shader_errors
+                .insert(pipeline_key.to_owned(), (shader, message));
+        }
+    }
+
+    /// Shaders of the worker which failed to compile, by type path, along with the
+    /// compilation errors. Shaders of passes added with
+    /// [`AppComputeWorkerBuilder::add_pass_by_name`] are listed too when no resource
+    /// matches some of their variables.
+    ///
+    /// Shaders used with several sets of constants are listed once, with the error
+    /// of one of their variants.
+    ///
+    /// Passes using them fail with [`Error::PipelineFailed`] until they're fixed.
+    pub fn shader_errors(&self) -> impl Iterator<Item = (&str, &str)> {
+        let mut shaders = HashSet::new();
+        self.shader_errors
+            .values()
+            .filter(move |(shader, _)| shaders.insert(shader.as_str()))
+            .map(|(shader, message)| (shader.as_str(), message.as_str()))
+    }
+
+    /// Whether the pipeline of the compute pass `step` is ready to run.
+    #[inline]
+    pub fn try_pipeline_state(&self, step: impl Into<StepId>) -> Result<PipelineState> {
+        let pipeline_key = self.pass_pipeline_key(&step.into())?;
+
+        if self.shader_errors.contains_key(pipeline_key) {
+            return Ok(PipelineState::Failed);
+        }
+
+        Ok(match self.pipelines.get(pipeline_key) {
+            Some(Some(_)) => PipelineState::Ready,
+            _ => PipelineState::Queued,
+        })
+    }
+
+    /// Whether the pipeline of the compute pass `step` is ready to run.
+    /// In case of error, this function will panic.
+    #[inline]
+    pub fn pipeline_state(&self, step: impl Into<StepId>) -> PipelineState {
+        self.try_pipeline_state(step).unwrap()
+    }
+
+    /// Why the pipeline of the compute pass `step` failed, as listed by
+    /// [`Self::shader_errors`]. `None` unless its state is [`PipelineState::Failed`].
+    #[inline]
+    pub fn try_pipeline_error(&self, step: impl Into<StepId>) -> Result<Option<&str>> {
+        let pipeline_key = self.pass_pipeline_key(&step.into())?;
+
+        Ok(self
+            .shader_errors
+            .get(pipeline_key)
+            .map(|(_, message)| message.as_str()))
     }
 
-    pub(crate) fn extract_pipelines(mut worker: ResMut<Self>, pipeline_cache: Res<PipelineCache>) {
-        for (type_path, cached_id) in &worker.cached_pipeline_ids.clone() {
-            let Some(pipeline) = worker.pipelines.get(type_path) else {
+    /// Why the pipeline of the compute pass `step` failed, as listed by
+    /// [`Self::shader_errors`]. `None` unless its state is [`PipelineState::Failed`].
+    /// In case of error, this function will panic.
+    #[inline]
+    pub fn pipeline_error(&self, step: impl Into<StepId>) -> Option<&str> {
+        self.try_pipeline_error(step).unwrap()
+    }
+
+    /// Key of the pipeline used by the compute pass `step`.
+    fn pass_pipeline_key(&self, step: &StepId) -> Result<&str> {
+        let index = self.step_index(step)?;
+
+        let StepKind::ComputePass(compute_pass) = &self.steps[index].kind else {
+            return Err(Error::InvalidStep(format!("{:?}", self.steps[index])));
+        };
+
+        Ok(&compute_pass.pipeline_key)
+    }
+
+    /// Bind the globals of the shader to the resources with the same name, in passes
+    /// using the pipeline `pipeline_key` which were added with
+    /// [`AppComputeWorkerBuilder::add_pass_by_name`].
+    fn bind_by_name(&mut self, pipeline_key: &str, shader_bindings: &[ShaderBinding]) {
+        for step in &mut self.steps {
+            let StepKind::ComputePass(compute_pass) = &mut step.kind else {
                 continue;
             };
 
-            if pipeline.is_some() {
+            if !compute_pass.bind_by_name || compute_pass.pipeline_key != pipeline_key {
+                continue;
+            }
+
+            compute_pass.bindings = shader_bindings
+                .iter()
+                .map(|shader_binding| PassBinding {
+                    group: shader_binding.group,
+                    binding: shader_binding.binding,
+                    name: shader_binding.name.clone().unwrap_or_default(),
+                })
+                .collect();
+        }
+    }
+
+    /// Compute the workgroup counts of passes dispatched over a number of elements,
+    /// once their pipeline is ready.
+    fn resolve_element_workgroups(&mut self, pipeline_cache: &PipelineCache) {
+        for step in &mut self.steps {
+            let StepKind::ComputePass(ComputePass {
+                dispatch:
+                    Dispatch::Elements {
+                        elements,
+                        workgroups: workgroups @ None,
+                    },
+                pipeline_key,
+                ..
+            }) = &mut step.kind
+            else {
                 continue;
             };
 
-            let cached_id = *cached_id;
+            if !matches!(self.pipelines.get(pipeline_key), Some(Some(_))) {
+                continue;
+            }
 
-            worker.pipelines.insert(
-                type_path.clone(),
-                pipeline_cache.get_compute_pipeline(cached_id).cloned(),
-            );
+            let Some(workgroup_size) = self
+                .cached_pipeline_ids
+                .get(pipeline_key)
+                .and_then(|id| pipeline_cache.get_compute_pipeline_workgroup_size(*id))
+            else {
+                continue;
+            };
+
+            *workgroups = Some([
+                elements[0].div_ceil(workgroup_size[0]),
+                elements[1].div_ceil(workgroup_size[1]),
+                elements[2].div_ceil(workgroup_size[2]),
+            ]);
         }
     }
 
     pub fn get_buffer(&self, target: &str) -> Option<&Buffer> {
         self.buffers.get(target)
     }
+
+    pub fn get_texture(&self, target: &str) -> Option<&Texture> {
+        self.textures.get(target).map(|texture| &texture.texture)
+    }
+
+    pub fn get_texture_view(&self, target: &str) -> Option<&TextureView> {
+        self.textures.get(target).map(|texture| &texture.view)
+    }
+
+    pub fn get_sampler(&self, target: &str) -> Option<&Sampler> {
+        self.samplers.get(target)
+    }
+
+    pub fn get_image(&self, target: &str) -> Option<&Handle<Image>> {
+        self.images.get(target)
+    }
 }
@@ -1,22 +1,36 @@
-use std::{borrow::Cow, marker::PhantomData, time::Duration};
+use std::{borrow::Cow, marker::PhantomData, ops::Range, sync::Arc, time::Duration};
 
 use bevy::{
+    asset::{Assets, Handle},
+    image::Image,
     platform::collections::HashMap,
     prelude::{AssetServer, World},
     render::{
         render_resource::{
-            Buffer, ComputePipelineDescriptor, ShaderRef, ShaderType,
+            Buffer, ComputePipelineDescriptor, Sampler, ShaderDefVal, ShaderRef, ShaderType,
             encase::{StorageBuffer, UniformBuffer, private::WriteInto},
         },
-        renderer::RenderDevice,
+        renderer::{RenderDevice, RenderQueue},
     },
 };
-use wgpu::{BufferDescriptor, BufferUsages, util::BufferInitDescriptor};
+use bytemuck::{NoUninit, bytes_of, cast_slice};
+use wgpu::{
+    BufferDescriptor, BufferUsages, COPY_BUFFER_ALIGNMENT, COPY_BYTES_PER_ROW_ALIGNMENT, Extent3d,
+    Features, SamplerDescriptor, StorageTextureAccess, TextureDescriptor, TextureDimension,
+    TextureFormat, TextureUsages, TextureViewDescriptor,
+    util::{BufferInitDescriptor, TextureDataOrder},
+};
 
 use crate::{
-    pipeline_cache::{AppCachedComputePipelineId, PipelineCache},
+    error::BuildError,
+    images::{COMPUTE_IMAGE_USAGES, ComputeImages},
+    pipeline_cache::{AppCachedComputePipelineId, Constants, PipelineCache},
     traits::{ComputeShader, ComputeWorker},
-    worker::{AppComputeWorker, ComputePass, RunMode, StagingBuffer, Step},
+    worker::{
+        AppComputeWorker, BufferKind, ComputePass, Dispatch, PassBinding, ReadbackPolicy, RunMode,
+        StagingBuffer, Step, StepKind, TextureCopyLayout, WorkerTexture, WorkgroupsFn,
+        fits_indirect_args,
+    },
 };
 
 /// A builder struct to build [`AppComputeWorker<W>`]
@@ -25,6 +39,10 @@ pub struct AppComputeWorkerBuilder<'a, W: ComputeWorker> {
     pub(crate) world: &'a mut World,
     pub(crate) cached_pipeline_ids: HashMap<String, AppCachedComputePipelineId>,
     pub(crate) buffers: HashMap<String, Buffer>,
+    pub(crate) buffer_kinds: HashMap<String, BufferKind>,
+    pub(crate) textures: HashMap<String, WorkerTexture>,
+    pub(crate) samplers: HashMap<String, Sampler>,
+    pub(crate) images: HashMap<String, Handle<Image>>,
     pub(crate) staging_buffers: HashMap<String, StagingBuffer>,
     pub(crate) steps: Vec<Step>,
     pub(crate) run_mode: RunMode,
@@ -34,7 +52,14 @@ pub struct AppComputeWorkerBuilder<'a, W: ComputeWorker> {
     ///
     /// 0 seconds means the shader will immediately be polled synchronously. None emeans the shader will only run asynchronously.
     pub(crate) maximum_async_time: Option<Duration>,
-    extra_buffer_usages: Option<BufferUsages>,
+    /// How many runs may be submitted before the oldest one finished.
+    pub(crate) frames_in_flight: usize,
+    pub(crate) strict: bool,
+    /// Whether the worker measures the timings of its runs.
+    pub(crate) profile: bool,
+    pub(crate) extra_buffer_usages: Option<BufferUsages>,
+    /// Problems found while adding resources and steps, reported by [`Self::try_build`].
+    errors: Vec<BuildError>,
     _phantom: PhantomData<W>,
 }
 
@@ -47,28 +72,48 @@ impl<'a, W: ComputeWorker> AppComputeWorkerBuilder<'a, W> {
             world,
             cached_pipeline_ids: HashMap::default(),
             buffers: HashMap::default(),
+            buffer_kinds: HashMap::default(),
+            textures: HashMap::default(),
+            samplers: HashMap::default(),
+            images: HashMap::default(),
             staging_buffers: HashMap::default(),
             steps: vec![],
             run_mode: RunMode::Continuous,
             maximum_async_time: Some(Duration::from_secs(0)),
+            frames_in_flight: 1,
+            strict: false,
+            profile: false,
             extra_buffer_usages: None,
+            errors: vec![],
             _phantom: PhantomData,
         }
     }
 
     /// Add a new uniform buffer to the worker, and fill it with `uniform`.
     pub fn add_uniform<T: ShaderType + WriteInto>(&mut self, name: &str, uniform: &T) -> &mut Self {
+        if !self.check_name(name) {
+            return self;
+        }
+
         T::assert_uniform_compat();
         let mut buffer = UniformBuffer::new(Vec::new());
-        buffer.write::<T>(uniform).unwrap();
+        if let Err(error) = buffer.write::<T>(uniform) {
+            self.errors.push(BuildError::Encoding {
+                name: name.to_owned(),
+                message: error.to_string(),
+            });
+            return self;
+        }
 
         let render_device = self.world.resource::<RenderDevice>();
 
-        let mut usage = BufferUsages::COPY_DST | BufferUsages::UNIFORM;
+        let mut usage = BufferUsages::COPY_DST | BufferUsages::COPY_SRC | BufferUsages::UNIFORM;
         if let Some(extra_usages) = self.extra_buffer_usages {
             usage |= extra_usages;
         }
 
+        self.buffer_kinds
+            .insert(name.to_owned(), BufferKind::Uniform);
         self.buffers.insert(
             name.to_owned(),
             render_device.create_buffer_with_data(&BufferInitDescriptor {
@@ -82,16 +127,28 @@ impl<'a, W: ComputeWorker> AppComputeWorkerBuilder<'a, W> {
 
     /// Add a new storage buffer to the worker, and fill it with `storage`. It will be read only.
     pub fn add_storage<T: ShaderType + WriteInto>(&mut self, name: &str, storage: &T) -> &mut Self {
+        if !self.check_name(name) {
+            return self;
+        }
+
         let mut buffer = StorageBuffer::new(Vec::new());
-        buffer.write::<T>(storage).unwrap();
+        if let Err(error) = buffer.write::<T>(storage) {
+            self.errors.push(BuildError::Encoding {
+                name: name.to_owned(),
+                message: error.to_string(),
+            });
+            return self;
+        }
 
         let render_device = self.world.resource::<RenderDevice>();
 
-        let mut usage = BufferUsages::COPY_DST | BufferUsages::STORAGE;
+        let mut usage = BufferUsages::COPY_DST | BufferUsages::COPY_SRC | BufferUsages::STORAGE;
         if let Some(extra_usages) = self.extra_buffer_usages {
             usage |= extra_usages;
         }
 
+        self.buffer_kinds
+            .insert(name.to_owned(), BufferKind::Storage { read_only: true });
         self.buffers.insert(
             name.to_owned(),
             render_device.create_buffer_with_data(&BufferInitDescriptor {
@@ -109,8 +166,18 @@ impl<'a, W: ComputeWorker> AppComputeWorkerBuilder<'a, W> {
         name: &str,
         storage: &T,
     ) -> &mut Self {
+        if !self.check_name(name) {
+            return self;
+        }
+
         let mut buffer = StorageBuffer::new(Vec::new());
-        buffer.write::<T>(storage).unwrap();
+        if let Err(error) = buffer.write::<T>(storage) {
+            self.errors.push(BuildError::Encoding {
+                name: name.to_owned(),
+                message: error.to_string(),
+            });
+            return self;
+        }
 
         let render_device = self.world.resource::<RenderDevice>();
 
@@ -119,6 +186,8 @@ impl<'a, W: ComputeWorker> AppComputeWorkerBuilder<'a, W> {
             usage |= extra_usages;
         }
 
+        self.buffer_kinds
+            .insert(name.to_owned(), BufferKind::Storage { read_only: false });
         self.buffers.insert(
             name.to_owned(),
             render_device.create_buffer_with_data(&BufferInitDescriptor {
@@ -135,35 +204,81 @@ impl<'a, W: ComputeWorker> AppComputeWorkerBuilder<'a, W> {
     /// your shaders.
     /// The buffer will be filled with `data`
     pub fn add_staging<T: ShaderType + WriteInto>(&mut self, name: &str, data: &T) -> &mut Self {
+        if !self.check_name(name) {
+            return self;
+        }
+
         self.add_rw_storage(name, data);
-        let buffer = self.buffers.get(name).unwrap();
+        let Some(buffer) = self.buffers.get(name) else {
+            return self;
+        };
 
         let render_device = self.world.resource::<RenderDevice>();
 
-        let staging = StagingBuffer {
-            mapped: true,
-            buffer: render_device.create_buffer(&BufferDescriptor {
+        let staging = StagingBuffer::new(
+            render_device.create_buffer(&BufferDescriptor {
                 label: Some(name),
                 size: buffer.size(),
                 usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
                 mapped_at_creation: true,
             }),
-        };
+            None,
+        );
 
         self.staging_buffers.insert(name.to_owned(), staging);
 
         self
     }
 
+    /// Add a new read/write storage buffer holding indirect dispatch arguments,
+    /// one `[x, y, z]` workgroup count per dispatch.
+    ///
+    /// Shaders can bind it as an `array<u32>` to write the arguments, and
+    /// [`Self::add_pass_indirect`] reads the ones of the `index`-th dispatch at
+    /// `offset` `12 * index` bytes.
+    pub fn add_indirect_args(&mut self, name: &str, args: &[[u32; 3]]) -> &mut Self {
+        if !self.check_name(name) {
+            return self;
+        }
+
+        let render_device = self.world.resource::<RenderDevice>();
+
+        let mut usage = BufferUsages::COPY_DST
+            | BufferUsages::COPY_SRC
+            | BufferUsages::STORAGE
+            | BufferUsages::INDIRECT;
+        if let Some(extra_usages) = self.extra_buffer_usages {
+            usage |= extra_usages;
+        }
+
+        self.buffer_kinds
+            .insert(name.to_owned(), BufferKind::Storage { read_only: false });
+        self.buffers.insert(
+            name.to_owned(),
+            render_device.create_buffer_with_data(&BufferInitDescriptor {
+                label: Some(name),
+                contents: cast_slice(args),
+                usage,
+            }),
+        );
+        self
+    }
+
     /// Add a new empty uniform buffer to the worker.
     pub fn add_empty_uniform(&mut self, name: &str, size: u64) -> &mut Self {
+        if !self.check_name(name) {
+            return self;
+        }
+
         let render_device = self.world.resource::<RenderDevice>();
 
-        let mut usage = BufferUsages::COPY_DST | BufferUsages::UNIFORM;
+        let mut usage = BufferUsages::COPY_DST | BufferUsages::COPY_SRC | BufferUsages::UNIFORM;
         if let Some(extra_usages) = self.extra_buffer_usages {
             usage |= extra_usages;
         }
 
+        self.buffer_kinds
+            .insert(name.to_owned(), BufferKind::Uniform);
         self.buffers.insert(
             name.to_owned(),
             render_device.create_buffer(&BufferDescriptor {
@@ -179,13 +294,19 @@ impl<'a, W: ComputeWorker> AppComputeWorkerBuilder<'a, W> {
 
     /// Add a new empty storage buffer to the worker. It will be read only.
     pub fn add_empty_storage(&mut self, name: &str, size: u64) -> &mut Self {
+        if !self.check_name(name) {
+            return self;
+        }
+
         let render_device = self.world.resource::<RenderDevice>();
 
-        let mut usage = BufferUsages::COPY_DST | BufferUsages::STORAGE;
+        let mut usage = BufferUsages::COPY_DST | BufferUsages::COPY_SRC | BufferUsages::STORAGE;
         if let Some(extra_usages) = self.extra_buffer_usages {
             usage |= extra_usages;
         }
 
+        self.buffer_kinds
+            .insert(name.to_owned(), BufferKind::Storage { read_only: true });
         self.buffers.insert(
             name.to_owned(),
             render_device.create_buffer(&BufferDescriptor {
@@ -200,6 +321,10 @@ impl<'a, W: ComputeWorker> AppComputeWorkerBuilder<'a, W> {
 
     /// Add a new empty read/write storage buffer to the worker.
     pub fn add_empty_rw_storage(&mut self, name: &str, size: u64) -> &mut Self {
+        if !self.check_name(name) {
+            return self;
+        }
+
         let render_device = self.world.resource::<RenderDevice>();
 
         let mut usage = BufferUsages::COPY_DST | BufferUsages::COPY_SRC | BufferUsages::STORAGE;
@@ -207,6 +332,8 @@ impl<'a, W: ComputeWorker> AppComputeWorkerBuilder<'a, W> {
             usage |= extra_usages;
         }
 
+        self.buffer_kinds
+            .insert(name.to_owned(), BufferKind::Storage { read_only: false });
         self.buffers.insert(
             name.to_owned(),
             render_device.create_buffer(&BufferDescriptor {
@@ -224,68 +351,555 @@ impl<'a, W: ComputeWorker> AppComputeWorkerBuilder<'a, W> {
     /// your shaders.
     /// The buffer will empty.
     pub fn add_empty_staging(&mut self, name: &str, size: u64) -> &mut Self {
+        if !self.check_name(name) {
+            return self;
+        }
+
         self.add_empty_rw_storage(name, size);
 
-        let buffer = self.buffers.get(name).unwrap();
+        let Some(buffer) = self.buffers.get(name) else {
+            return self;
+        };
 
         let render_device = self.world.resource::<RenderDevice>();
 
-        let staging = StagingBuffer {
-            mapped: true,
-            buffer: render_device.create_buffer(&BufferDescriptor {
+        let staging = StagingBuffer::new(
+            render_device.create_buffer(&BufferDescriptor {
                 label: Some(name),
                 size: buffer.size(),
                 usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
                 mapped_at_creation: true,
             }),
+            None,
+        );
+
+        self.staging_buffers.insert(name.to_owned(), staging);
+
+        self
+    }
+
+    /// Add a new sampled texture to the worker, and fill it with `data`.
+    ///
+    /// `data` must be tightly packed texels, layer after layer.
+    pub fn add_texture(
+        &mut self,
+        name: &str,
+        size: Extent3d,
+        dimension: TextureDimension,
+        format: TextureFormat,
+        data: &[u8],
+    ) -> &mut Self {
+        if !self.check_name(name) {
+            return self;
+        }
+
+        let texture = self.create_texture(
+            name,
+            size,
+            dimension,
+            format,
+            TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+            Some(data),
+        );
+
+        self.textures.insert(name.to_owned(), texture);
+        self
+    }
+
+    /// Add a new empty sampled texture to the worker.
+    pub fn add_empty_texture(
+        &mut self,
+        name: &str,
+        size: Extent3d,
+        dimension: TextureDimension,
+        format: TextureFormat,
+    ) -> &mut Self {
+        if !self.check_name(name) {
+            return self;
+        }
+
+        let texture = self.create_texture(
+            name,
+            size,
+            dimension,
+            format,
+            TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+            None,
+        );
+
+        self.textures.insert(name.to_owned(), texture);
+        self
+    }
+
+    /// Add a new empty storage texture to the worker.
+    ///
+    /// `access` must match the access mode declared in your shader. Textures
+    /// that are only read can also be bound as sampled textures in other passes.
+    pub fn add_storage_texture(
+        &mut self,
+        name: &str,
+        size: Extent3d,
+        dimension: TextureDimension,
+        format: TextureFormat,
+        access: StorageTextureAccess,
+    ) -> &mut Self {
+        if !self.check_name(name) {
+            return self;
+        }
+
+        let usage = storage_texture_usage(access);
+        let texture = self.create_texture(name, size, dimension, format, usage, None);
+
+        self.textures.insert(name.to_owned(), texture);
+        self
+    }
+
+    /// Create a storage texture along with a staging buffer to read it back
+    /// from the CPU with [`AppComputeWorker::read_texture`].
+    ///
+    /// Depth, stencil and compressed formats can't be read back, building fails
+    /// with them.
+    pub fn add_staging_texture(
+        &mut self,
+        name: &str,
+        size: Extent3d,
+        dimension: TextureDimension,
+        format: TextureFormat,
+        access: StorageTextureAccess,
+    ) -> &mut Self {
+        if !self.check_name(name) {
+            return self;
+        }
+
+        // Texels of depth, stencil and compressed formats can't be copied one by one.
+        let Some(block_size) = format
+            .block_copy_size(None)
+            .filter(|_| format.block_dimensions() == (1, 1))
+        else {
+            self.errors.push(BuildError::UnsupportedStagingFormat {
+                name: name.to_owned(),
+                format,
+            });
+            return self;
         };
 
+        // Read back whatever the access, even textures the shader only reads.
+        let usage = storage_texture_usage(access) | TextureUsages::COPY_SRC;
+        let texture = self.create_texture(name, size, dimension, format, usage, None);
+        self.textures.insert(name.to_owned(), texture);
+
+        let bytes_per_row = size.width * block_size;
+        let padded_bytes_per_row =
+            bytes_per_row.div_ceil(COPY_BYTES_PER_ROW_ALIGNMENT) * COPY_BYTES_PER_ROW_ALIGNMENT;
+
+        let render_device = self.world.resource::<RenderDevice>();
+
+        let staging = StagingBuffer::new(
+            render_device.create_buffer(&BufferDescriptor {
+                label: Some(name),
+                size: padded_bytes_per_row as u64
+                    * size.height as u64
+                    * size.depth_or_array_layers as u64,
+                usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+                mapped_at_creation: true,
+            }),
+            Some(TextureCopyLayout {
+                size,
+                bytes_per_row,
+                padded_bytes_per_row,
+            }),
+        );
+
         self.staging_buffers.insert(name.to_owned(), staging);
 
         self
     }
 
+    /// Set when the staging buffer or staging texture `name` is read back.
+    /// See [`ReadbackPolicy`].
+    pub fn set_readback(&mut self, name: &str, policy: ReadbackPolicy) -> &mut Self {
+        let Some(staging) = self.staging_buffers.get_mut(name) else {
+            self.errors.push(BuildError::NotStaging(name.to_owned()));
+            return self;
+        };
+
+        staging.policy = policy;
+        self
+    }
+
+    /// Only read back the bytes in `range` of the staging buffer `name`, instead of
+    /// the whole buffer. Reads then return these bytes only.
+    ///
+    /// The range must be aligned to [`COPY_BUFFER_ALIGNMENT`] bytes. Staging
+    /// textures are always read back whole.
+    pub fn set_readback_range(&mut self, name: &str, range: Range<u64>) -> &mut Self {
+        let Some(staging) = self.staging_buffers.get(name) else {
+            self.errors.push(BuildError::NotStaging(name.to_owned()));
+            return self;
+        };
+
+        let size = self.buffers.get(name).map_or(0, |buffer| buffer.size());
+        if staging.texture_layout.is_some()
+            || range.is_empty()
+            || range.end > size
+            || !range.start.is_multiple_of(COPY_BUFFER_ALIGNMENT)
+            || !range.end.is_multiple_of(COPY_BUFFER_ALIGNMENT)
+        {
+            self.errors.push(BuildError::InvalidReadbackRange {
+                name: name.to_owned(),
+                start: range.start,
+                end: range.end,
+            });
+            return self;
+        }
+
+        let render_device = self.world.resource::<RenderDevice>();
+        let mut resized = StagingBuffer::new(
+            render_device.create_buffer(&BufferDescriptor {
+                label: Some(name),
+                size: range.end - range.start,
+                usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+                mapped_at_creation: true,
+            }),
+            None,
+        );
+        resized.policy = staging.policy;
+        resized.offset = range.start;
+
+        self.staging_buffers.insert(name.to_owned(), resized);
+        self
+    }
+
+    /// Add a new sampler to the worker.
+    pub fn add_sampler(&mut self, name: &str, descriptor: &SamplerDescriptor) -> &mut Self {
+        if !self.check_name(name) {
+            return self;
+        }
+
+        let render_device = self.world.resource::<RenderDevice>();
+
+        self.samplers.insert(
+            name.to_owned(),
+            render_device.create_sampler(&SamplerDescriptor {
+                label: Some(name),
+                ..descriptor.clone()
+            }),
+        );
+        self
+    }
+
+    /// Bind an [`Image`] asset as a storage texture of the worker.
+    ///
+    /// Your shaders write directly into the texture of the image's `GpuImage`,
+    /// so sprites and materials using `image` display the result without any copy.
+    /// Passes only run once the image has been loaded and prepared in the render world.
+    ///
+    /// The image format must support storage bindings, eg: `Rgba8Unorm` but not `Rgba8UnormSrgb`.
+    /// Images can only be swapped with other images whose textures have the same
+    /// descriptor, which is checked when the swap runs.
+    pub fn add_image(&mut self, name: &str, image: &Handle<Image>) -> &mut Self {
+        if !self.check_name(name) {
+            return self;
+        }
+
+        self.world.resource::<ComputeImages>().track(image.id());
+
+        let mut images = self.world.resource_mut::<Assets<Image>>();
+        if let Some(image) = images.get_mut(image) {
+            image.texture_descriptor.usage |= COMPUTE_IMAGE_USAGES;
+        }
+
+        self.images.insert(name.to_owned(), image.clone());
+        self
+    }
+
+    /// Add `image` to the [`Image`] assets and bind it as a storage texture of the worker.
+    ///
+    /// Use [`AppComputeWorker::get_image`] to retrieve its handle.
+    pub fn add_new_image(&mut self, name: &str, mut image: Image) -> &mut Self {
+        image.texture_descriptor.usage |= COMPUTE_IMAGE_USAGES;
+        let handle = self.world.resource_mut::<Assets<Image>>().add(image);
+        self.add_image(name, &handle)
+    }
+
+    fn create_texture(
+        &self,
+        name: &str,
+        size: Extent3d,
+        dimension: TextureDimension,
+        format: TextureFormat,
+        usage: TextureUsages,
+        data: Option<&[u8]>,
+    ) -> WorkerTexture {
+        let render_device = self.world.resource::<RenderDevice>();
+
+        let descriptor = TextureDescriptor {
+            label: Some(name),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension,
+            format,
+            usage,
+            view_formats: &[],
+        };
+
+        let texture = match data {
+            Some(data) => {
+                let render_queue = self.world.resource::<RenderQueue>();
+                render_device.create_texture_with_data(
+                    render_queue,
+                    &descriptor,
+                    TextureDataOrder::LayerMajor,
+                    data,
+                )
+            }
+            None => render_device.create_texture(&descriptor),
+        };
+
+        let view = texture.create_view(&TextureViewDescriptor {
+            label: Some(name),
+            ..Default::default()
+        });
+
+        WorkerTexture { texture, view }
+    }
+
     /// Add a new compute pass to your worker.
     /// They will run sequentially in the order you insert them.
     pub fn add_pass<S: ComputeShader>(&mut self, workgroups: [u32; 3], vars: &[&str]) -> &mut Self {
-        if !self.cached_pipeline_ids.contains_key(S::type_path()) {
-            let pipeline_cache = self.world.resource::<PipelineCache>();
+        self.add_pass_with_constants::<S>(workgroups, vars, &[])
+    }
+
+    /// Add a new compute pass to your worker, specialising the pipeline-overridable
+    /// constants of `S` with `constants`.
+    ///
+    /// They are merged with [`ComputeShader::constants`], values from `constants` taking
+    /// precedence. Each distinct set of constants compiles its own pipeline, so a single
+    /// shader can be used with different values within a worker.
+    pub fn add_pass_with_constants<S: ComputeShader>(
+        &mut self,
+        workgroups: [u32; 3],
+        vars: &[&str],
+        constants: &[(&str, f64)],
+    ) -> &mut Self {
+        self.add_compute_pass::<S>(
+            Dispatch::Direct(workgroups),
+            Some(vars_bindings(vars)),
+            constants,
+        )
+    }
+
+    /// Add a new compute pass to your worker, reading its workgroup counts from
+    /// `args_buffer` at `offset` bytes when it runs.
+    ///
+    /// This lets previous passes compute the size of the dispatch on the GPU.
+    /// `args_buffer` must have been created with [`BufferUsages::INDIRECT`], see
+    /// [`Self::add_indirect_args`], and `offset` must be a multiple of 4 followed by
+    /// 12 bytes of arguments. Building fails otherwise.
+    pub fn add_pass_indirect<S: ComputeShader>(
+        &mut self,
+        args_buffer: &str,
+        offset: u64,
+        vars: &[&str],
+    ) -> &mut Self {
+        self.add_compute_pass::<S>(
+            Dispatch::Indirect {
+                buffer: args_buffer.to_owned(),
+                offset,
+            },
+            Some(vars_bindings(vars)),
+            &[],
+        )
+    }
+
+    /// Add a new compute pass to your worker, dispatching enough workgroups to run
+    /// the shader once per element of an `elements` sized grid.
+    ///
+    /// The workgroup counts are the element counts divided by the `@workgroup_size`
+    /// of the entry point, rounded up, so shaders should ignore invocations outside
+    /// of the grid. The workgroup size is read from the shader once its pipeline is
+    /// ready, it may be set by pipeline-overridable constants.
+    pub fn add_pass_for_elements<S: ComputeShader>(
+        &mut self,
+        elements: [u32; 3],
+        vars: &[&str],
+    ) -> &mut Self {
+        let dispatch = Dispatch::Elements {
+            elements,
+            workgroups: None,
+        };
+        self.add_compute_pass::<S>(dispatch, Some(vars_bindings(vars)), &[])
+    }
 
+    /// Add a new compute pass to your worker, whose workgroup counts are evaluated
+    /// from the main world before every run.
+    ///
+    /// Use it when the size of the work changes over time, eg. with the number of
+    /// particles stored in a resource. See also [`AppComputeWorker::set_workgroups`].
+    pub fn add_pass_dynamic<S: ComputeShader>(
+        &mut self,
+        workgroups: impl Fn(&World) -> [u32; 3] + Send + Sync + 'static,
+        vars: &[&str],
+    ) -> &mut Self {
+        let dispatch = Dispatch::Dynamic {
+            workgroups: workgroups(self.world),
+            update: WorkgroupsFn(Arc::new(workgroups)),
+        };
+        self.add_compute_pass::<S>(dispatch, Some(vars_bindings(vars)), &[])
+    }
+
+    /// Add a new compute pass to your worker, binding resources at explicit indices.
+    ///
+    /// Each `(group, binding, name)` binds the buffer, texture or sampler called `name`
+    /// at `@group(group) @binding(binding)`, so that a pass can use several bind groups.
+    /// Running the pass fails if its shader uses a binding which wasn't supplied.
+    pub fn add_pass_with_bindings<S: ComputeShader>(
+        &mut self,
+        workgroups: [u32; 3],
+        bindings: &[(u32, u32, &str)],
+    ) -> &mut Self {
+        let bindings = bindings
+            .iter()
+            .map(|(group, binding, name)| PassBinding {
+                group: *group,
+                binding: *binding,
+                name: name.to_string(),
+            })
+            .collect();
+        self.add_compute_pass::<S>(Dispatch::Direct(workgroups), Some(bindings), &[])
+    }
+
+    /// Add a new compute pass to your worker, binding each resource used by the shader
+    /// to the buffer, texture or sampler with the same name as its WGSL variable.
+    ///
+    /// Variables are matched once the shader is processed. Those no resource matches are
+    /// listed by [`AppComputeWorker::shader_errors`], and running the pass fails.
+    pub fn add_pass_by_name<S: ComputeShader>(&mut self, workgroups: [u32; 3]) -> &mut Self {
+        self.add_compute_pass::<S>(Dispatch::Direct(workgroups), None, &[])
+    }
+
+    /// Queue the pipeline of `S` and add a pass using it, `bindings` are matched by
+    /// name with the globals of the shader when `None`.
+    fn add_compute_pass<S: ComputeShader>(
+        &mut self,
+        dispatch: Dispatch,
+        bindings: Option<Vec<PassBinding>>,
+        constants: &[(&str, f64)],
+    ) -> &mut Self {
+        let constants = pass_constants::<S>(constants);
+        let pipeline_key = pipeline_key(S::type_path(), &constants);
+
+        if !self.cached_pipeline_ids.contains_key(&pipeline_key) {
+            let pipeline_cache = self.world.resource::<PipelineCache>();
             let asset_server = self.world.resource::<AssetServer>();
-            let shader = match S::shader() {
-                ShaderRef::Default => None,
-                ShaderRef::Handle(handle) => Some(handle),
-                ShaderRef::Path(path) => Some(asset_server.load(path)),
+
+            if let Some(descriptor) = pipeline_descriptor::<S>(asset_server, &constants) {
+                let cached_id = pipeline_cache.queue_compute_pipeline(descriptor, constants);
+
+                self.cached_pipeline_ids.insert(
+                    pipeline_key.clone(),
+                    AppCachedComputePipelineId(cached_id.id()),
+                );
+            } else {
+                let error = BuildError::MissingShader(S::type_path().to_owned());
+                if !self.errors.contains(&error) {
+                    self.errors.push(error);
+                }
             }
-            .unwrap();
-
-            let cached_id = pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
-                label: None,
-                layout: S::layouts().to_vec(),
-                push_constant_ranges: S::push_constant_ranges().to_vec(),
-                shader_defs: S::shader_defs().to_vec(),
-                entry_point: Cow::Borrowed(S::entry_point()),
-                shader,
-                zero_initialize_workgroup_memory: false,
-            });
+        }
+
+        self.steps.push(Step {
+            label: None,
+            enabled: true,
+            kind: StepKind::ComputePass(ComputePass {
+                dispatch,
+                bind_by_name: bindings.is_none(),
+                bindings: bindings.unwrap_or_default(),
+                shader_type_path: S::type_path().to_string(),
+                pipeline_key,
+                push_constant_range: ComputePass::push_constant_range::<S>(),
+                push_constants: None,
+            }),
+        });
+        self
+    }
 
-            self.cached_pipeline_ids.insert(
-                S::type_path().to_string(),
-                AppCachedComputePipelineId(cached_id.id()),
-            );
+    /// Set the initial push constants of the last added compute pass.
+    ///
+    /// They must exactly fill the ranges declared by [`ComputeShader::push_constant_ranges`]
+    /// for compute shaders, and can be updated later on with
+    /// [`AppComputeWorker::set_push_constants`].
+    ///
+    /// Building fails if the last step isn't a compute pass, if `data` doesn't fit,
+    /// or if the device doesn't support [`Features::PUSH_CONSTANTS`].
+    pub fn set_push_constants<T: NoUninit>(&mut self, data: &T) -> &mut Self {
+        let index = self.steps.len().saturating_sub(1);
+        let Some(step) = self.steps.last_mut() else {
+            self.errors.push(BuildError::PushConstantsWithoutPass);
+            return self;
+        };
+        let id = step.id(index);
+        let StepKind::ComputePass(compute_pass) = &mut step.kind else {
+            self.errors.push(BuildError::PushConstantsWithoutPass);
+            return self;
+        };
+
+        let render_device = self.world.resource::<RenderDevice>();
+        if !render_device.features().contains(Features::PUSH_CONSTANTS) {
+            self.errors.push(BuildError::PushConstantsUnsupported(
+                compute_pass.shader_type_path.clone(),
+            ));
+            return self;
+        }
+
+        let data = bytes_of(data);
+        if !compute_pass.fits_push_constants(data.len()) {
+            self.errors.push(BuildError::PushConstantsMismatch {
+                step: id.to_string(),
+                size: data.len(),
+                range: compute_pass.push_constant_range.clone().unwrap_or_default(),
+            });
+            return self;
         }
 
-        self.steps.push(Step::ComputePass(ComputePass {
-            workgroups,
-            vars: vars.iter().map(|a| String::from(*a)).collect(),
-            shader_type_path: S::type_path().to_string(),
-        }));
+        compute_pass.push_constants = Some(data.to_vec());
         self
     }
 
+    /// Swap two buffers, or two textures, between passes.
     pub fn add_swap(&mut self, buffer_a: &str, buffer_b: &str) -> &mut Self {
-        self.steps
-            .push(Step::Swap(buffer_a.to_owned(), buffer_b.to_owned()));
+        self.steps.push(Step {
+            label: None,
+            enabled: true,
+            kind: StepKind::Swap(buffer_a.to_owned(), buffer_b.to_owned()),
+        });
+        self
+    }
+
+    /// Label the last added step, so that it can be referred to by its label
+    /// instead of its index at runtime. See [`StepId`](crate::prelude::StepId).
+    ///
+    /// The label also names the step in GPU captures, where unlabeled passes are
+    /// named after their shader. Building fails if no step has been added yet, or
+    /// if another step has the same label.
+    pub fn label(&mut self, label: &str) -> &mut Self {
+        if self
+            .steps
+            .iter()
+            .any(|step| step.label.as_deref() == Some(label))
+        {
+            self.errors
+                .push(BuildError::DuplicateLabel(label.to_owned()));
+            return self;
+        }
+
+        let Some(step) = self.steps.last_mut() else {
+            self.errors
+                .push(BuildError::LabelWithoutStep(label.to_owned()));
+            return self;
+        };
+        step.label = Some(label.to_owned());
         self
     }
 
@@ -310,6 +924,27 @@ impl<'a, W: ComputeWorker> AppComputeWorkerBuilder<'a, W> {
         self
     }
 
+    /// Panic when running the worker fails, instead of sending a
+    /// [`ComputeWorkerError`](crate::prelude::ComputeWorkerError) event and
+    /// stopping the worker. Mostly useful in tests.
+    ///
+    /// Strict workers also refuse to bind buffers added with [`Self::add_storage`]
+    /// to `read_write` variables, which otherwise only logs a warning.
+    pub fn strict(&mut self) -> &mut Self {
+        self.strict = true;
+        self
+    }
+
+    /// Measure how long each run takes, see [`AppComputeWorker::timings`].
+    ///
+    /// On devices supporting [`Features::TIMESTAMP_QUERY`], every compute pass is
+    /// timed on the GPU. Otherwise only the time between submitting a run and its
+    /// completion is measured, on the CPU.
+    pub fn profile(&mut self) -> &mut Self {
+        self.profile = true;
+        self
+    }
+
     /// The worker will block the frame it is run on until it compltes. This is the default behavior
     pub fn synchronous(&mut self) -> &mut Self {
         self.maximum_async_time = Some(Duration::from_secs(0));
@@ -332,8 +967,242 @@ impl<'a, W: ComputeWorker> AppComputeWorkerBuilder<'a, W> {
         self
     }
 
+    /// Let the worker submit up to `frames` runs before the oldest one finished,
+    /// instead of waiting for each run to finish before starting the next one.
+    ///
+    /// Every staging buffer is allocated `frames` times, and reads return the results
+    /// of the most recently finished run while the next ones are still computing.
+    /// Each copy stays mapped once read back, until a new run copies to it again.
+    /// This is mostly useful along with [`Self::asynchronous`]. Defaults to 1, and
+    /// is at least 1.
+    pub fn frames_in_flight(&mut self, frames: usize) -> &mut Self {
+        self.frames_in_flight = frames.max(1);
+        self
+    }
+
+    /// Build an [`AppComputeWorker<W>`] from this builder, or return every
+    /// configuration problem found in it.
+    ///
+    /// Besides the problems met while adding resources and steps, passes and swaps
+    /// are checked to only refer to existing resources, and swapped buffers to have
+    /// the same size. Passes binding resources by name are checked once their shader
+    /// is processed instead.
+    pub fn try_build(&self) -> Result<AppComputeWorker<W>, Vec<BuildError>> {
+        let mut errors = self.errors.clone();
+
+        for (index, step) in self.steps.iter().enumerate() {
+            let step_id = step.id(index).to_string();
+            let unknown_resource = |name: &str| BuildError::UnknownResource {
+                step: step_id.clone(),
+                name: name.to_owned(),
+            };
+
+            match &step.kind {
+                StepKind::ComputePass(compute_pass) => {
+                    let indirect_buffer = match &compute_pass.dispatch {
+                        Dispatch::Indirect { buffer, .. } => Some(buffer.as_str()),
+                        _ => None,
+                    };
+
+                    if let Dispatch::Indirect { buffer, offset } = &compute_pass.dispatch {
+                        match self.buffers.get(buffer) {
+                            Some(args) if !fits_indirect_args(args, *offset) => {
+                                errors.push(BuildError::InvalidIndirectArgs {
+                                    step: step_id.clone(),
+                                    buffer: buffer.clone(),
+                                    offset: *offset,
+                                    size: args.size(),
+                                    usage: args.usage(),
+                                });
+                            }
+                            _ => {}
+                        }
+                    }
+
+                    let names = compute_pass
+                        .bindings
+                        .iter()
+                        .map(|binding| binding.name.as_str())
+                        .chain(indirect_buffer);
+                    for name in names {
+                        if !self.has_resource(name) {
+                            errors.push(unknown_resource(name));
+                        }
+                    }
+                }
+                StepKind::Swap(a, b) => {
+                    if !self.has_resource(a) || !self.has_resource(b) {
+                        errors.extend(
+                            [a, b]
+                                .into_iter()
+                                .filter(|name| !self.has_resource(name))
+                                .map(|name| unknown_resource(name)),
+                        );
+                        continue;
+                    }
+
+                    let swap_kind_mismatch = || BuildError::SwapKindMismatch {
+                        step: step_id.clone(),
+                        a: a.clone(),
+                        b: b.clone(),
+                    };
+
+                    match (self.buffers.get(a), self.buffers.get(b)) {
+                        (Some(buffer_a), Some(buffer_b)) => {
+                            if buffer_a.size() != buffer_b.size() {
+                                errors.push(BuildError::SwapSizeMismatch {
+                                    step: step_id.clone(),
+                                    a: a.clone(),
+                                    b: b.clone(),
+                                    size_a: buffer_a.size(),
+                                    size_b: buffer_b.size(),
+                                });
+                            }
+                        }
+                        (None, None) => match (self.textures.get(a), self.textures.get(b)) {
+                            (Some(texture_a), Some(texture_b)) => {
+                                if !same_texture_descriptor(texture_a, texture_b) {
+                                    errors.push(swap_kind_mismatch());
+                                }
+                            }
+                            // Images are only checked once loaded, when the swap runs.
+                            (None, None)
+                                if self.images.contains_key(a) && self.images.contains_key(b) => {}
+                            _ => errors.push(swap_kind_mismatch()),
+                        },
+                        _ => errors.push(swap_kind_mismatch()),
+                    }
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(AppComputeWorker::from(self))
+    }
+
     /// Build an [`AppComputeWorker<W>`] from this builder.
+    ///
+    /// Panics listing every configuration problem if there are any, see [`Self::try_build`].
     pub fn build(&self) -> AppComputeWorker<W> {
-        AppComputeWorker::from(self)
+        self.try_build().unwrap_or_else(|errors| {
+            let errors = errors
+                .iter()
+                .map(|error| format!("\n- {error}"))
+                .collect::<String>();
+            panic!("Couldn't build the compute worker:{errors}")
+        })
+    }
+
+    /// Whether a buffer, texture, sampler or image called `name` was added.
+    fn has_resource(&self, name: &str) -> bool {
+        self.buffers.contains_key(name)
+            || self.textures.contains_key(name)
+            || self.samplers.contains_key(name)
+            || self.images.contains_key(name)
+    }
+
+    /// Record an error if a resource called `name` was already added, returning
+    /// whether the name is available.
+    fn check_name(&mut self, name: &str) -> bool {
+        if self.has_resource(name) {
+            self.errors.push(BuildError::DuplicateName(name.to_owned()));
+            return false;
+        }
+
+        true
+    }
+}
+
+/// Usages of a storage texture bound with `access`.
+fn storage_texture_usage(access: StorageTextureAccess) -> TextureUsages {
+    let mut usage = TextureUsages::STORAGE_BINDING | TextureUsages::COPY_DST;
+    if access != StorageTextureAccess::WriteOnly {
+        usage |= TextureUsages::TEXTURE_BINDING;
     }
+    if access != StorageTextureAccess::ReadOnly {
+        usage |= TextureUsages::COPY_SRC;
+    }
+    usage
+}
+
+/// Whether textures `a` and `b` can be swapped.
+pub(crate) fn same_texture_descriptor(a: &WorkerTexture, b: &WorkerTexture) -> bool {
+    a.texture.size() == b.texture.size()
+        && a.texture.dimension() == b.texture.dimension()
+        && a.texture.format() == b.texture.format()
+        && a.texture.usage() == b.texture.usage()
+}
+
+/// Bind `vars` to the group 0, in order.
+pub(crate) fn vars_bindings(vars: &[&str]) -> Vec<PassBinding> {
+    vars.iter()
+        .enumerate()
+        .map(|(binding, name)| PassBinding {
+            group: 0,
+            binding: binding as u32,
+            name: name.to_string(),
+        })
+        .collect()
+}
+
+/// Constants of a pass using `S`, [`ComputeShader::constants`] overridden by `constants`.
+pub(crate) fn pass_constants<S: ComputeShader>(constants: &[(&str, f64)]) -> Constants {
+    S::constants()
+        .iter()
+        .chain(constants)
+        .map(|(name, value)| (name.to_string(), *value))
+        .collect()
+}
+
+/// Descriptor of the pipeline of `S` specialised with `constants`, unless it doesn't
+/// return a shader.
+pub(crate) fn pipeline_descriptor<S: ComputeShader>(
+    asset_server: &AssetServer,
+    constants: &Constants,
+) -> Option<ComputePipelineDescriptor> {
+    let shader = match S::shader() {
+        ShaderRef::Default => return None,
+        ShaderRef::Handle(handle) => handle,
+        ShaderRef::Path(path) => asset_server.load(path),
+    };
+
+    // The GL backend of wgpu caches programs by shader module, ignoring the constants
+    // of the pipeline. Give every set of constants a shader module of its own.
+    let mut shader_defs = S::shader_defs().to_vec();
+    if !constants.is_empty() {
+        shader_defs.push(ShaderDefVal::Bool(
+            pipeline_key("CONSTANTS", constants),
+            true,
+        ));
+    }
+
+    Some(ComputePipelineDescriptor {
+        label: Some(Cow::Borrowed(S::type_path())),
+        layout: S::layouts().to_vec(),
+        push_constant_ranges: S::push_constant_ranges().to_vec(),
+        shader_defs,
+        entry_point: Cow::Borrowed(S::entry_point()),
+        shader,
+        zero_initialize_workgroup_memory: false,
+    })
+}
+
+/// Key identifying the pipeline of a shader specialised with `constants`.
+///
+/// Shaders without constants simply use their type path.
+pub(crate) fn pipeline_key(type_path: &str, constants: &Constants) -> String {
+    if constants.is_empty() {
+        return type_path.to_owned();
+    }
+
+    let mut constants = constants
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<_>>();
+    constants.sort();
+
+    format!("{type_path}{{{}}}", constants.join(","))
 }
@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+use bevy_app_compute::prelude::*;
+
+mod common;
+
+#[derive(TypePath)]
+struct GroupsShader;
+
+impl ComputeShader for GroupsShader {
+    fn shader() -> ShaderRef {
+        "shaders/groups.wgsl".into()
+    }
+}
+
+#[derive(Resource)]
+struct ByNameComputeWorker;
+
+impl ComputeWorker for ByNameComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            // Declared in another order than in the shader
+            .add_staging("values", &[1., 2., 3., 4.])
+            .add_uniform("scale", &3f32)
+            .add_pass_by_name::<GroupsShader>([4, 1, 1])
+            .build()
+    }
+}
+
+#[derive(Resource)]
+struct UnmatchedComputeWorker;
+
+impl ComputeWorker for UnmatchedComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_staging("values", &[1., 2., 3., 4.])
+            .add_uniform("factor", &3f32)
+            .add_pass_by_name::<GroupsShader>([4, 1, 1])
+            .build()
+    }
+}
+
+#[test]
+fn binds_resources_by_variable_name() {
+    fn test(compute_worker: ResMut<AppComputeWorker<ByNameComputeWorker>>) {
+        let result: Vec<f32> = compute_worker.read_vec("values");
+        assert_eq!(result, [3., 6., 9., 12.]);
+        assert_eq!(compute_worker.pipeline_state(0), PipelineState::Ready);
+    }
+
+    let mut app = common::build_app::<ByNameComputeWorker>();
+    app.add_systems(Update, test);
+    app.update();
+}
+
+#[test]
+fn reports_unmatched_variable_names() {
+    let mut app = common::setup_app::<UnmatchedComputeWorker>();
+
+    for _ in 0..10 {
+        app.update();
+    }
+
+    let worker = app
+        .world()
+        .resource::<AppComputeWorker<UnmatchedComputeWorker>>();
+    assert_eq!(*worker.state(), WorkerState::Failed);
+
+    let shader_errors = worker.shader_errors().collect::<Vec<_>>();
+    assert_eq!(shader_errors.len(), 1);
+    assert_eq!(shader_errors[0].0, GroupsShader::type_path());
+    assert!(shader_errors[0].1.contains("`scale`"));
+    assert_eq!(worker.pipeline_state(0), PipelineState::Failed);
+    assert_eq!(worker.pipeline_error(0), Some(shader_errors[0].1));
+}
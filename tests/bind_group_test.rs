@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+use bevy_app_compute::prelude::*;
+
+mod common;
+
+#[derive(TypePath)]
+struct AddShader;
+
+impl ComputeShader for AddShader {
+    fn shader() -> ShaderRef {
+        "shaders/first_pass.wgsl".into()
+    }
+}
+
+#[derive(Resource)]
+struct SwapComputeWorker;
+
+impl ComputeWorker for SwapComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_uniform("value", &1f32)
+            .add_staging("input", &[0f32; 4])
+            .add_staging("output", &[0f32; 4])
+            .add_pass::<AddShader>([4, 1, 1], &["value", "input", "output"])
+            .add_swap("input", "output")
+            .build()
+    }
+}
+
+fn read_input(app: &App) -> Vec<f32> {
+    app.world()
+        .resource::<AppComputeWorker<SwapComputeWorker>>()
+        .read_vec("input")
+}
+
+#[test]
+fn binds_the_swapped_buffers_on_every_run() {
+    let mut app = common::build_app::<SwapComputeWorker>();
+    let first = read_input(&app);
+
+    // Each run must bind the buffers swapped by the previous one
+    for run in 1..=4 {
+        app.update();
+        let expected: Vec<f32> = first.iter().map(|value| value + run as f32).collect();
+        assert_eq!(read_input(&app), expected);
+    }
+}
@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+use bevy_app_compute::prelude::*;
+
+mod common;
+
+#[derive(TypePath)]
+struct GroupsShader;
+
+impl ComputeShader for GroupsShader {
+    fn shader() -> ShaderRef {
+        "shaders/groups.wgsl".into()
+    }
+}
+
+#[derive(Resource)]
+struct GroupsComputeWorker;
+
+impl ComputeWorker for GroupsComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_uniform("scale", &2f32)
+            .add_staging("values", &[1., 2., 3., 4.])
+            .add_pass_with_bindings::<GroupsShader>([4, 1, 1], &[(0, 0, "scale"), (1, 0, "values")])
+            .build()
+    }
+}
+
+#[derive(Resource)]
+struct MissingBindingComputeWorker;
+
+impl ComputeWorker for MissingBindingComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .strict()
+            .add_staging("values", &[1., 2., 3., 4.])
+            .add_pass_with_bindings::<GroupsShader>([4, 1, 1], &[(1, 0, "values")])
+            .build()
+    }
+}
+
+#[derive(Resource)]
+struct NoBindingsComputeWorker;
+
+impl ComputeWorker for NoBindingsComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .strict()
+            .add_staging("values", &[1., 2., 3., 4.])
+            .add_pass_with_bindings::<GroupsShader>([4, 1, 1], &[])
+            .build()
+    }
+}
+
+#[derive(Resource)]
+struct FirstGroupComputeWorker;
+
+impl ComputeWorker for FirstGroupComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .strict()
+            .add_uniform("scale", &2f32)
+            .add_staging("values", &[1., 2., 3., 4.])
+            .add_pass_with_bindings::<GroupsShader>([4, 1, 1], &[(0, 0, "scale")])
+            .build()
+    }
+}
+
+#[derive(Resource)]
+struct ExtraBindingComputeWorker;
+
+impl ComputeWorker for ExtraBindingComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .strict()
+            .add_uniform("scale", &2f32)
+            .add_staging("values", &[1., 2., 3., 4.])
+            .add_pass_with_bindings::<GroupsShader>(
+                [4, 1, 1],
+                &[(0, 0, "scale"), (1, 0, "values"), (1, 1, "values")],
+            )
+            .build()
+    }
+}
+
+#[test]
+fn binds_multiple_groups() {
+    fn test(compute_worker: ResMut<AppComputeWorker<GroupsComputeWorker>>) {
+        let result: Vec<f32> = compute_worker.read_vec("values");
+        assert_eq!(result, [2., 4., 6., 8.]);
+    }
+
+    let mut app = common::build_app::<GroupsComputeWorker>();
+    app.add_systems(Update, test);
+    app.update();
+}
+
+#[test]
+#[should_panic(expected = "MissingBinding")]
+fn reports_missing_bindings() {
+    common::build_app::<MissingBindingComputeWorker>();
+}
+
+#[test]
+#[should_panic(expected = "MissingBinding")]
+fn reports_missing_bindings_of_passes_without_bindings() {
+    common::build_app::<NoBindingsComputeWorker>();
+}
+
+#[test]
+#[should_panic(expected = "MissingBinding")]
+fn reports_missing_groups() {
+    common::build_app::<FirstGroupComputeWorker>();
+}
+
+#[test]
+#[should_panic(expected = "UnexpectedBinding")]
+fn reports_extra_bindings() {
+    common::build_app::<ExtraBindingComputeWorker>();
+}
@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+use bevy_app_compute::prelude::*;
+
+mod common;
+
+#[derive(TypePath)]
+struct SimpleShader;
+
+impl ComputeShader for SimpleShader {
+    fn shader() -> ShaderRef {
+        "shaders/simple.wgsl".into()
+    }
+}
+
+#[derive(TypePath)]
+struct DefaultShader;
+
+impl ComputeShader for DefaultShader {
+    fn shader() -> ShaderRef {
+        ShaderRef::Default
+    }
+}
+
+#[derive(Resource)]
+struct SimpleComputeWorker;
+
+impl ComputeWorker for SimpleComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_uniform("uni", &5f32)
+            .add_staging("values", &[1f32, 2., 3., 4.])
+            .add_pass::<SimpleShader>([4, 1, 1], &["uni", "values"])
+            .build()
+    }
+}
+
+fn misconfigured_builder(world: &mut World) -> AppComputeWorkerBuilder<'_, SimpleComputeWorker> {
+    let mut builder = AppComputeWorkerBuilder::new(world);
+    builder
+        .label("too early")
+        .add_uniform("uni", &5f32)
+        .add_uniform("uni", &6f32)
+        .add_staging("values", &[1f32, 2., 3., 4.])
+        .add_staging("other", &[1f32, 2.])
+        .add_pass::<SimpleShader>([4, 1, 1], &["uni", "missing"])
+        .add_swap("values", "other")
+        .add_pass::<DefaultShader>([1, 1, 1], &["values"]);
+    builder
+}
+
+#[test]
+fn collects_configuration_problems() {
+    let mut app = common::build_app::<SimpleComputeWorker>();
+
+    let Err(errors) = misconfigured_builder(app.world_mut()).try_build() else {
+        panic!("The worker shouldn't build.");
+    };
+
+    assert_eq!(
+        errors,
+        [
+            BuildError::LabelWithoutStep("too early".to_owned()),
+            BuildError::DuplicateName("uni".to_owned()),
+            BuildError::MissingShader(DefaultShader::type_path().to_owned()),
+            BuildError::UnknownResource {
+                step: "#0".to_owned(),
+                name: "missing".to_owned(),
+            },
+            BuildError::SwapSizeMismatch {
+                step: "#1".to_owned(),
+                a: "values".to_owned(),
+                b: "other".to_owned(),
+                size_a: 16,
+                size_b: 8,
+            },
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Step #0 uses `missing`")]
+fn build_panics_with_every_problem() {
+    let mut app = common::build_app::<SimpleComputeWorker>();
+    misconfigured_builder(app.world_mut()).build();
+}
+
+#[test]
+fn rejects_duplicate_labels() {
+    let mut app = common::build_app::<SimpleComputeWorker>();
+
+    let mut builder = AppComputeWorkerBuilder::<SimpleComputeWorker>::new(app.world_mut());
+    builder
+        .add_uniform("uni", &5f32)
+        .add_staging("values", &[1f32, 2., 3., 4.])
+        .add_pass::<SimpleShader>([4, 1, 1], &["uni", "values"])
+        .label("scale")
+        .add_pass::<SimpleShader>([4, 1, 1], &["uni", "values"])
+        .label("scale");
+
+    let Err(errors) = builder.try_build() else {
+        panic!("The worker shouldn't build.");
+    };
+
+    assert_eq!(errors, [BuildError::DuplicateLabel("scale".to_owned())]);
+}
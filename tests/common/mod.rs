@@ -1,3 +1,6 @@
+// Not every test uses every helper.
+#![allow(dead_code)]
+
 use bevy::{prelude::*, winit::WinitPlugin};
 use bevy_app_compute::prelude::{
     AppComputePlugin, AppComputeWorker, AppComputeWorkerPlugin, ComputeWorker,
@@ -11,7 +14,8 @@ use bevy_app_compute::prelude::{
 // compute workers should take.
 const MAX_FRAMES_TO_READY: i16 = 10;
 
-pub fn build_app<T>() -> App
+/// Build an app running the compute worker `T`, without waiting for it to be ready.
+pub fn setup_app<T>() -> App
 where
     T: ComputeWorker,
 {
@@ -21,6 +25,14 @@ where
         .add_plugins(AppComputeWorkerPlugin::<T>::default());
     app.finish();
     app.cleanup();
+    app
+}
+
+pub fn build_app<T>() -> App
+where
+    T: ComputeWorker,
+{
+    let mut app = setup_app::<T>();
 
     let mut is_ready = false;
     for _ in 0..MAX_FRAMES_TO_READY {
@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+use bevy_app_compute::prelude::*;
+
+mod common;
+
+#[derive(TypePath)]
+struct MultiplyShader;
+
+impl ComputeShader for MultiplyShader {
+    fn shader() -> ShaderRef {
+        "shaders/constants.wgsl".into()
+    }
+
+    fn constants<'a>() -> &'a [(&'a str, f64)] {
+        &[("MULTIPLIER", 2.)]
+    }
+}
+
+#[derive(Resource)]
+struct ConstantsComputeWorker;
+
+impl ComputeWorker for ConstantsComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_staging("values", &[1., 2., 3., 4.])
+            // Takes precedence over `MultiplyShader::constants()`
+            .add_pass_with_constants::<MultiplyShader>(
+                [4, 1, 1],
+                &["values"],
+                &[("MULTIPLIER", 3.)],
+            )
+            .build()
+    }
+}
+
+#[derive(Resource)]
+struct TwoConstantsComputeWorker;
+
+impl ComputeWorker for TwoConstantsComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_staging("doubled", &[1., 2., 3., 4.])
+            .add_staging("tripled", &[1., 2., 3., 4.])
+            .add_pass::<MultiplyShader>([4, 1, 1], &["doubled"])
+            .add_pass_with_constants::<MultiplyShader>(
+                [4, 1, 1],
+                &["tripled"],
+                &[("MULTIPLIER", 3.)],
+            )
+            .build()
+    }
+}
+
+#[test]
+fn overrides_shader_constants() {
+    fn test(compute_worker: ResMut<AppComputeWorker<ConstantsComputeWorker>>) {
+        let result: Vec<f32> = compute_worker.read_vec("values");
+        assert_eq!(result, [3., 6., 9., 12.]);
+    }
+
+    let mut app = common::build_app::<ConstantsComputeWorker>();
+    app.add_systems(Update, test);
+    app.update();
+}
+
+#[test]
+fn specialises_a_shader_per_constant_set() {
+    fn test(compute_worker: ResMut<AppComputeWorker<TwoConstantsComputeWorker>>) {
+        let doubled: Vec<f32> = compute_worker.read_vec("doubled");
+        let tripled: Vec<f32> = compute_worker.read_vec("tripled");
+        assert_eq!(doubled, [2., 4., 6., 8.]);
+        assert_eq!(tripled, [3., 6., 9., 12.]);
+    }
+
+    let mut app = common::build_app::<TwoConstantsComputeWorker>();
+    app.add_systems(Update, test);
+    app.update();
+}
@@ -0,0 +1,60 @@
+use bevy::{diagnostic::DiagnosticsStore, prelude::*};
+use bevy_app_compute::prelude::*;
+
+mod common;
+
+#[derive(TypePath)]
+struct SimpleShader;
+
+impl ComputeShader for SimpleShader {
+    fn shader() -> ShaderRef {
+        "shaders/simple.wgsl".into()
+    }
+}
+
+#[derive(Resource)]
+struct SimpleComputeWorker;
+
+impl ComputeWorker for SimpleComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_uniform("uni", &1f32)
+            .add_staging("values", &[1f32, 2., 3., 4.])
+            .add_pass::<SimpleShader>([4, 1, 1], &["uni", "values"])
+            .build()
+    }
+}
+
+#[test]
+fn measures_diagnostics() {
+    let mut app = common::build_app::<SimpleComputeWorker>();
+
+    app.world_mut()
+        .resource_mut::<AppComputeWorker<SimpleComputeWorker>>()
+        .write("uni", &2f32);
+    app.update();
+
+    let diagnostics = app.world().resource::<DiagnosticsStore>();
+    let value = |path| {
+        diagnostics
+            .get_measurement(&path)
+            .map(|measurement| measurement.value)
+    };
+
+    // Passes are only timed on profiled workers.
+    assert!(value(ComputeWorkerDiagnostics::<SimpleComputeWorker>::gpu_time()).is_none());
+    assert!(value(ComputeWorkerDiagnostics::<SimpleComputeWorker>::submit_to_complete()).is_some());
+    assert!(value(ComputeWorkerDiagnostics::<SimpleComputeWorker>::encode_time()).is_some());
+    assert_eq!(
+        value(ComputeWorkerDiagnostics::<SimpleComputeWorker>::bytes_uploaded()),
+        Some(4.)
+    );
+    assert_eq!(
+        value(ComputeWorkerDiagnostics::<SimpleComputeWorker>::bytes_read_back()),
+        Some(16.)
+    );
+    assert_eq!(
+        value(ComputeWorkerDiagnostics::<SimpleComputeWorker>::sync_fallbacks()),
+        Some(0.)
+    );
+}
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+use bevy_app_compute::prelude::*;
+
+mod common;
+
+#[derive(TypePath)]
+struct ElementsShader;
+
+impl ComputeShader for ElementsShader {
+    fn shader() -> ShaderRef {
+        "shaders/elements.wgsl".into()
+    }
+}
+
+#[derive(Resource)]
+struct ElementsComputeWorker;
+
+impl ComputeWorker for ElementsComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_staging("invocations", &0u32)
+            .add_pass_for_elements::<ElementsShader>([5, 1, 1], &["invocations"])
+            .build()
+    }
+}
+
+#[test]
+fn computes_workgroups_from_shader_workgroup_size() {
+    fn test(compute_worker: ResMut<AppComputeWorker<ElementsComputeWorker>>) {
+        // 2 workgroups of 4 invocations
+        assert_eq!(compute_worker.read::<u32>("invocations"), 8);
+    }
+
+    let mut app = common::build_app::<ElementsComputeWorker>();
+    app.add_systems(Update, test);
+    app.update();
+}
@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use bevy_app_compute::{error::Error, prelude::*};
+
+mod common;
+
+#[derive(TypePath)]
+struct GroupsShader;
+
+impl ComputeShader for GroupsShader {
+    fn shader() -> ShaderRef {
+        "shaders/groups.wgsl".into()
+    }
+}
+
+#[derive(Resource)]
+struct MissingBindingComputeWorker;
+
+impl ComputeWorker for MissingBindingComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_staging("values", &[1., 2., 3., 4.])
+            .add_pass_with_bindings::<GroupsShader>([4, 1, 1], &[(1, 0, "values")])
+            .build()
+    }
+}
+
+#[derive(Resource, Default)]
+struct Failures(Vec<String>);
+
+fn record_failures(
+    mut events: EventReader<ComputeWorkerError<MissingBindingComputeWorker>>,
+    mut failures: ResMut<Failures>,
+) {
+    for event in events.read() {
+        assert!(matches!(event.error, Error::MissingBinding { .. }));
+        failures.0.push(event.error.to_string());
+    }
+}
+
+#[test]
+fn sends_error_events_instead_of_panicking() {
+    let mut app = common::setup_app::<MissingBindingComputeWorker>();
+    app.init_resource::<Failures>()
+        .add_systems(Last, record_failures);
+
+    for _ in 0..10 {
+        app.update();
+    }
+
+    let worker = app
+        .world()
+        .resource::<AppComputeWorker<MissingBindingComputeWorker>>();
+    assert_eq!(*worker.state(), WorkerState::Failed);
+    // Failed workers stop running until executed again.
+    assert_eq!(app.world().resource::<Failures>().0.len(), 1);
+
+    app.world_mut()
+        .resource_mut::<AppComputeWorker<MissingBindingComputeWorker>>()
+        .execute();
+    app.update();
+
+    assert_eq!(app.world().resource::<Failures>().0.len(), 2);
+}
@@ -0,0 +1,80 @@
+use bevy::{diagnostic::FrameCount, prelude::*};
+use bevy_app_compute::prelude::*;
+
+mod common;
+
+#[derive(TypePath)]
+struct SimpleShader;
+
+impl ComputeShader for SimpleShader {
+    fn shader() -> ShaderRef {
+        "shaders/simple.wgsl".into()
+    }
+}
+
+#[derive(Resource)]
+struct OneShotComputeWorker;
+
+impl ComputeWorker for OneShotComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_uniform("uni", &1f32)
+            .add_staging("values", &[1f32, 2., 3., 4.])
+            .add_pass::<SimpleShader>([4, 1, 1], &["uni", "values"])
+            .one_shot()
+            .build()
+    }
+}
+
+#[derive(Resource, Default)]
+struct Finished {
+    frames: Vec<u32>,
+    results: Vec<Vec<f32>>,
+}
+
+fn record_finished(
+    mut events: EventReader<ComputeWorkerFinished<OneShotComputeWorker>>,
+    frame_count: Res<FrameCount>,
+    mut finished: ResMut<Finished>,
+) {
+    for event in events.read() {
+        assert!(event.frame <= frame_count.0);
+        finished.frames.push(event.frame);
+    }
+}
+
+fn read_results(
+    worker: Res<AppComputeWorker<OneShotComputeWorker>>,
+    mut finished: ResMut<Finished>,
+) {
+    finished.results.push(worker.read_vec("values"));
+}
+
+#[test]
+fn sends_an_event_when_a_run_finishes() {
+    let mut app = common::setup_app::<OneShotComputeWorker>();
+    app.init_resource::<Finished>().add_systems(
+        Update,
+        (
+            record_finished,
+            read_results.run_if(on_compute_finished::<OneShotComputeWorker>()),
+        ),
+    );
+
+    // Let the pipeline compile, one-shot workers don't run on their own.
+    for _ in 0..5 {
+        app.update();
+    }
+    assert!(app.world().resource::<Finished>().frames.is_empty());
+
+    app.world_mut()
+        .resource_mut::<AppComputeWorker<OneShotComputeWorker>>()
+        .execute();
+    for _ in 0..5 {
+        app.update();
+    }
+
+    let finished = app.world().resource::<Finished>();
+    assert_eq!(finished.frames.len(), 1);
+    assert_eq!(finished.results, [[2., 3., 4., 5.]]);
+}
@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+use bevy_app_compute::prelude::*;
+
+mod common;
+
+#[derive(TypePath)]
+struct SimpleShader;
+
+impl ComputeShader for SimpleShader {
+    fn shader() -> ShaderRef {
+        "shaders/simple.wgsl".into()
+    }
+}
+
+#[derive(Resource)]
+struct PipelinedComputeWorker;
+
+impl ComputeWorker for PipelinedComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_uniform("uni", &1f32)
+            .add_staging("values", &[1f32, 2., 3., 4.])
+            .add_pass::<SimpleShader>([4, 1, 1], &["uni", "values"])
+            .asynchronous(None)
+            .frames_in_flight(3)
+            .build()
+    }
+}
+
+#[derive(Resource, Default)]
+struct Reads {
+    finished: usize,
+    results: Vec<(usize, Vec<f32>)>,
+}
+
+fn read_results(
+    mut events: EventReader<ComputeWorkerFinished<PipelinedComputeWorker>>,
+    worker: Res<AppComputeWorker<PipelinedComputeWorker>>,
+    mut reads: ResMut<Reads>,
+) {
+    reads.finished += events.read().count();
+    if worker.ready() {
+        let finished = reads.finished;
+        reads.results.push((finished, worker.read_vec("values")));
+    }
+}
+
+#[test]
+fn reads_the_most_recently_finished_run() {
+    let mut app = common::setup_app::<PipelinedComputeWorker>();
+    app.init_resource::<Reads>()
+        .add_systems(Update, read_results);
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    let reads = app.world().resource::<Reads>();
+    assert!(!reads.results.is_empty());
+    for (finished, values) in &reads.results {
+        // Every run adds 1, and runs finish in the order they were submitted.
+        let runs = *finished as f32;
+        assert_eq!(values, &[1. + runs, 2. + runs, 3. + runs, 4. + runs]);
+    }
+}
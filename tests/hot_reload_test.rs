@@ -0,0 +1,90 @@
+use bevy::{asset::weak_handle, prelude::*};
+use bevy_app_compute::prelude::*;
+
+mod common;
+
+const FILL_SHADER: Handle<Shader> = weak_handle!("5f0e0c38-4be4-4c64-9d87-2bb0d5c4a9e1");
+
+fn fill_shader(value: f32) -> Shader {
+    Shader::from_wgsl(
+        format!(
+            "@group(0) @binding(0)
+var<storage, read_write> values: array<f32>;
+
+@compute @workgroup_size(1)
+fn main(@builtin(global_invocation_id) invocation_id: vec3<u32>) {{
+    values[invocation_id.x] = {value:.1};
+}}"
+        ),
+        "fill.wgsl",
+    )
+}
+
+#[derive(TypePath)]
+struct FillShader;
+
+impl ComputeShader for FillShader {
+    fn shader() -> ShaderRef {
+        FILL_SHADER.into()
+    }
+}
+
+#[derive(Resource)]
+struct FillComputeWorker;
+
+impl ComputeWorker for FillComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_staging("values", &[0f32; 4])
+            .add_pass::<FillShader>([4, 1, 1], &["values"])
+            .build()
+    }
+}
+
+#[derive(Resource, Default)]
+struct Reloads(Vec<String>);
+
+fn record_reloads(
+    mut events: EventReader<ComputePipelineReloaded<FillComputeWorker>>,
+    mut reloads: ResMut<Reloads>,
+) {
+    reloads
+        .0
+        .extend(events.read().map(|event| event.shader.clone()));
+}
+
+/// Run `app` until the worker has filled its buffer with `value`.
+fn wait_for_value(app: &mut App, value: f32) {
+    for _ in 0..10 {
+        app.update();
+
+        let worker = app
+            .world()
+            .resource::<AppComputeWorker<FillComputeWorker>>();
+        if worker.ready() && worker.read_vec::<f32>("values") == [value; 4] {
+            return;
+        }
+    }
+
+    panic!("The worker didn't fill its buffer with {value}.");
+}
+
+#[test]
+fn uses_recompiled_pipelines() {
+    let mut app = common::setup_app::<FillComputeWorker>();
+    app.init_resource::<Reloads>()
+        .add_systems(Last, record_reloads);
+
+    let mut shaders = app.world_mut().resource_mut::<Assets<Shader>>();
+    shaders.insert(FILL_SHADER.id(), fill_shader(1.));
+    wait_for_value(&mut app, 1.);
+    assert!(app.world().resource::<Reloads>().0.is_empty());
+
+    let mut shaders = app.world_mut().resource_mut::<Assets<Shader>>();
+    shaders.insert(FILL_SHADER.id(), fill_shader(2.));
+    wait_for_value(&mut app, 2.);
+    assert_eq!(
+        app.world().resource::<Reloads>().0,
+        [FillShader::type_path()]
+    );
+}
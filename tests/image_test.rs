@@ -0,0 +1,197 @@
+use bevy::{
+    asset::RenderAssetUsages,
+    prelude::*,
+    render::render_resource::{Extent3d, StorageTextureAccess, TextureDimension, TextureFormat},
+};
+use bevy_app_compute::prelude::*;
+
+mod common;
+
+const SIZE: Extent3d = Extent3d {
+    width: 5,
+    height: 3,
+    depth_or_array_layers: 1,
+};
+
+#[derive(TypePath)]
+struct TextureShader;
+
+impl ComputeShader for TextureShader {
+    fn shader() -> ShaderRef {
+        "shaders/texture.wgsl".into()
+    }
+}
+
+/// Handle of the image, only added to the assets once the worker is built.
+#[derive(Resource)]
+struct ImageHandle(Handle<Image>);
+
+#[derive(Resource)]
+struct ImageComputeWorker;
+
+impl ComputeWorker for ImageComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        let image = world.resource::<Assets<Image>>().reserve_handle();
+        world.insert_resource(ImageHandle(image.clone()));
+
+        let input: Vec<u32> = (0..SIZE.width * SIZE.height).collect();
+
+        AppComputeWorkerBuilder::new(world)
+            .add_texture(
+                "input",
+                SIZE,
+                TextureDimension::D2,
+                TextureFormat::R32Uint,
+                bytemuck::cast_slice(&input),
+            )
+            .add_image("image", &image)
+            .add_staging_texture(
+                "output",
+                SIZE,
+                TextureDimension::D2,
+                TextureFormat::R32Uint,
+                StorageTextureAccess::WriteOnly,
+            )
+            .add_pass::<TextureShader>([SIZE.width, SIZE.height, 1], &["input", "image"])
+            .add_pass::<TextureShader>([SIZE.width, SIZE.height, 1], &["image", "output"])
+            .build()
+    }
+}
+
+#[derive(Resource)]
+struct SwappedImagesComputeWorker;
+
+impl ComputeWorker for SwappedImagesComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        let mut images = world.resource_mut::<Assets<Image>>();
+        let [a, b] = [1u32, 0].map(|value| {
+            images.add(Image::new_fill(
+                SIZE,
+                TextureDimension::D2,
+                &value.to_ne_bytes(),
+                TextureFormat::R32Uint,
+                RenderAssetUsages::RENDER_WORLD,
+            ))
+        });
+
+        AppComputeWorkerBuilder::new(world)
+            .add_image("a", &a)
+            .add_image("b", &b)
+            .add_staging_texture(
+                "output",
+                SIZE,
+                TextureDimension::D2,
+                TextureFormat::R32Uint,
+                StorageTextureAccess::WriteOnly,
+            )
+            .add_pass::<TextureShader>([SIZE.width, SIZE.height, 1], &["a", "b"])
+            .add_swap("a", "b")
+            .add_pass::<TextureShader>([SIZE.width, SIZE.height, 1], &["a", "output"])
+            .one_shot()
+            .build()
+    }
+}
+
+#[test]
+fn binds_images_once_loaded() {
+    let mut app = common::setup_app::<ImageComputeWorker>();
+
+    // Passes wait for the image, which isn't loaded yet.
+    for _ in 0..3 {
+        app.update();
+    }
+    let worker = app
+        .world()
+        .resource::<AppComputeWorker<ImageComputeWorker>>();
+    assert!(!worker.ready());
+
+    let handle = app.world().resource::<ImageHandle>().0.clone();
+    app.world_mut().resource_mut::<Assets<Image>>().insert(
+        &handle,
+        Image::new_fill(
+            SIZE,
+            TextureDimension::D2,
+            &0u32.to_ne_bytes(),
+            TextureFormat::R32Uint,
+            RenderAssetUsages::RENDER_WORLD,
+        ),
+    );
+
+    let mut ready = false;
+    for _ in 0..10 {
+        app.update();
+        if app
+            .world()
+            .resource::<AppComputeWorker<ImageComputeWorker>>()
+            .ready()
+        {
+            ready = true;
+            break;
+        }
+    }
+    assert!(ready, "The worker should run once the image is prepared.");
+
+    let worker = app
+        .world()
+        .resource::<AppComputeWorker<ImageComputeWorker>>();
+    let result: Vec<u32> = worker.read_texture("output");
+    let expected: Vec<u32> = (0..SIZE.width * SIZE.height).map(|v| v * 4).collect();
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn keeps_images_swapped_across_runs() {
+    let mut app = common::setup_app::<SwappedImagesComputeWorker>();
+
+    // Let the pipelines compile and the images be prepared.
+    for _ in 0..5 {
+        app.update();
+    }
+
+    // Each run doubles `a` into `b` then swaps them, so the output doubles every run
+    // only if the swap of the previous run stuck.
+    for expected in [4, 8, 16] {
+        app.world_mut()
+            .resource_mut::<AppComputeWorker<SwappedImagesComputeWorker>>()
+            .execute();
+        app.update();
+
+        let worker = app
+            .world()
+            .resource::<AppComputeWorker<SwappedImagesComputeWorker>>();
+        assert!(worker.ready());
+        let result: Vec<u32> = worker.read_texture("output");
+        assert_eq!(result, [expected; (SIZE.width * SIZE.height) as usize]);
+    }
+}
+
+#[test]
+fn rejects_swapping_images_with_textures() {
+    let mut app = common::setup_app::<ImageComputeWorker>();
+
+    let image = app.world().resource::<Assets<Image>>().reserve_handle();
+    let mut builder = AppComputeWorkerBuilder::<ImageComputeWorker>::new(app.world_mut());
+    builder
+        .add_image("image", &image)
+        .add_storage_texture(
+            "texture",
+            SIZE,
+            TextureDimension::D2,
+            TextureFormat::R32Uint,
+            StorageTextureAccess::WriteOnly,
+        )
+        .add_swap("image", "texture");
+
+    let Err(errors) = builder.try_build() else {
+        panic!("The worker shouldn't build.");
+    };
+
+    assert_eq!(
+        errors,
+        [BuildError::SwapKindMismatch {
+            step: "#0".to_owned(),
+            a: "image".to_owned(),
+            b: "texture".to_owned(),
+        }]
+    );
+}
@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+use bevy_app_compute::prelude::*;
+use wgpu::BufferUsages;
+
+mod common;
+
+#[derive(TypePath)]
+struct ArgsShader;
+
+impl ComputeShader for ArgsShader {
+    fn shader() -> ShaderRef {
+        "shaders/indirect_args.wgsl".into()
+    }
+}
+
+#[derive(TypePath)]
+struct DoubleShader;
+
+impl ComputeShader for DoubleShader {
+    fn shader() -> ShaderRef {
+        "shaders/indirect.wgsl".into()
+    }
+}
+
+#[derive(Resource)]
+struct IndirectComputeWorker;
+
+impl ComputeWorker for IndirectComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_uniform("count", &2u32)
+            .add_indirect_args("args", &[[0, 0, 0]])
+            .add_staging("values", &[1., 2., 3., 4.])
+            // The first pass decides how many values the second one doubles
+            .add_pass::<ArgsShader>([1, 1, 1], &["count", "args"])
+            .add_pass_indirect::<DoubleShader>("args", 0, &["values"])
+            .build()
+    }
+}
+
+#[test]
+fn dispatches_indirectly() {
+    fn test(compute_worker: ResMut<AppComputeWorker<IndirectComputeWorker>>) {
+        let result: Vec<f32> = compute_worker.read_vec("values");
+        assert_eq!(result, [2., 4., 3., 4.]);
+    }
+
+    let mut app = common::build_app::<IndirectComputeWorker>();
+    app.add_systems(Update, test);
+    app.update();
+}
+
+#[test]
+fn rejects_invalid_indirect_args() {
+    let mut app = common::build_app::<IndirectComputeWorker>();
+
+    let mut builder = AppComputeWorkerBuilder::<IndirectComputeWorker>::new(app.world_mut());
+    builder
+        .add_indirect_args("args", &[[1, 1, 1]])
+        .add_staging("values", &[1., 2., 3., 4.])
+        .add_pass_indirect::<DoubleShader>("args", 2, &["values"])
+        .add_pass_indirect::<DoubleShader>("args", 4, &["values"])
+        .add_pass_indirect::<DoubleShader>("values", 0, &["values"]);
+
+    let Err(errors) = builder.try_build() else {
+        panic!("The worker shouldn't build.");
+    };
+
+    let invalid: Vec<_> = errors
+        .iter()
+        .map(|error| match error {
+            BuildError::InvalidIndirectArgs {
+                step,
+                buffer,
+                offset,
+                usage,
+                ..
+            } => (
+                step.as_str(),
+                buffer.as_str(),
+                *offset,
+                usage.contains(BufferUsages::INDIRECT),
+            ),
+            error => panic!("Unexpected error: {error}"),
+        })
+        .collect();
+    // Unaligned, past the end, and without the `INDIRECT` usage.
+    assert_eq!(
+        invalid,
+        [
+            ("#0", "args", 2, true),
+            ("#1", "args", 4, true),
+            ("#2", "values", 0, false),
+        ]
+    );
+}
@@ -0,0 +1,129 @@
+use bevy::{prelude::*, tasks::AsyncComputeTaskPool};
+use bevy_app_compute::{error::Error, prelude::*};
+
+mod common;
+
+#[derive(TypePath)]
+struct SimpleShader;
+
+impl ComputeShader for SimpleShader {
+    fn shader() -> ShaderRef {
+        "shaders/simple.wgsl".into()
+    }
+}
+
+#[derive(Resource)]
+struct JobsComputeWorker;
+
+impl ComputeWorker for JobsComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_uniform("uni", &1f32)
+            .add_staging("values", &[0f32; 4])
+            .add_pass::<SimpleShader>([4, 1, 1], &["uni", "values"])
+            .one_shot()
+            .build()
+    }
+}
+
+#[derive(TypePath)]
+struct BrokenShader;
+
+impl ComputeShader for BrokenShader {
+    fn shader() -> ShaderRef {
+        "shaders/broken.wgsl".into()
+    }
+}
+
+#[derive(Resource)]
+struct BrokenJobsComputeWorker;
+
+impl ComputeWorker for BrokenJobsComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_staging("values", &[0f32; 4])
+            .add_pass::<BrokenShader>([4, 1, 1], &["values"])
+            .one_shot()
+            .build()
+    }
+}
+
+fn submit_job<W: ComputeWorker>(app: &mut App, job: ComputeJob) -> ComputeJobHandle {
+    app.world_mut()
+        .resource_mut::<AppComputeWorker<W>>()
+        .submit_job(job)
+}
+
+#[test]
+fn resolves_each_job_with_its_own_results() {
+    let mut app = common::setup_app::<JobsComputeWorker>();
+
+    let mut first = submit_job::<JobsComputeWorker>(
+        &mut app,
+        ComputeJob::new().write_slice("values", &[1f32, 2., 3., 4.]),
+    );
+    let second = submit_job::<JobsComputeWorker>(
+        &mut app,
+        ComputeJob::new()
+            .write("uni", &10f32)
+            .write_slice("values", &[10f32, 20., 30., 40.]),
+    );
+    let mut missing =
+        submit_job::<JobsComputeWorker>(&mut app, ComputeJob::new().write("missing", &0f32));
+
+    let second = AsyncComputeTaskPool::get().spawn(second);
+
+    for _ in 0..20 {
+        app.update();
+        if missing.is_finished() {
+            break;
+        }
+    }
+
+    // The task awaiting the second job may not have been polled again yet, block on it instead.
+    assert!(first.is_finished());
+    let first = first.take_result().unwrap().unwrap();
+    assert_eq!(first.read_vec::<f32>("values"), [2., 3., 4., 5.]);
+    assert!(matches!(
+        first.try_read::<[f32; 5]>("values"),
+        Err(Error::OutOfBounds { .. })
+    ));
+
+    let second = futures_lite::future::block_on(second).unwrap();
+    assert_eq!(second.read_vec::<f32>("values"), [20., 30., 40., 50.]);
+
+    assert!(missing.take_result().unwrap().is_err());
+}
+
+#[test]
+fn fails_queued_jobs_with_the_worker() {
+    let mut app = common::setup_app::<BrokenJobsComputeWorker>();
+
+    let mut running = submit_job::<BrokenJobsComputeWorker>(&mut app, ComputeJob::new());
+    let mut queued = submit_job::<BrokenJobsComputeWorker>(&mut app, ComputeJob::new());
+
+    for _ in 0..20 {
+        app.update();
+        if queued.is_finished() {
+            break;
+        }
+    }
+
+    assert!(running.take_result().unwrap().is_err());
+    assert!(queued.take_result().unwrap().is_err());
+}
+
+#[test]
+fn fails_queued_jobs_when_the_worker_is_dropped() {
+    let mut app = common::setup_app::<JobsComputeWorker>();
+
+    let mut queued = submit_job::<JobsComputeWorker>(&mut app, ComputeJob::new());
+    app.world_mut()
+        .remove_resource::<AppComputeWorker<JobsComputeWorker>>();
+
+    assert!(queued.is_finished());
+    assert!(matches!(
+        queued.take_result(),
+        Some(Err(Error::JobFailed(_)))
+    ));
+}
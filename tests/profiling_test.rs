@@ -0,0 +1,52 @@
+use bevy::{prelude::*, render::renderer::RenderDevice};
+use bevy_app_compute::prelude::*;
+use wgpu::Features;
+
+mod common;
+
+#[derive(TypePath)]
+struct SimpleShader;
+
+impl ComputeShader for SimpleShader {
+    fn shader() -> ShaderRef {
+        "shaders/simple.wgsl".into()
+    }
+}
+
+#[derive(Resource)]
+struct ProfiledComputeWorker;
+
+impl ComputeWorker for ProfiledComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_uniform("uni", &1f32)
+            .add_staging("values", &[1f32, 2., 3., 4.])
+            .add_pass::<SimpleShader>([4, 1, 1], &["uni", "values"])
+            .label("first")
+            .add_pass::<SimpleShader>([4, 1, 1], &["uni", "values"])
+            .profile()
+            .build()
+    }
+}
+
+#[test]
+fn times_every_pass() {
+    let app = common::build_app::<ProfiledComputeWorker>();
+
+    let worker = app
+        .world()
+        .resource::<AppComputeWorker<ProfiledComputeWorker>>();
+    let timings = worker.timings().expect("The run should've been timed.");
+
+    let features = app.world().resource::<RenderDevice>().features();
+    if features.contains(Features::TIMESTAMP_QUERY) {
+        let steps: Vec<_> = timings
+            .passes
+            .iter()
+            .map(|(step, _)| step.clone())
+            .collect();
+        assert_eq!(steps, [StepId::from("first"), StepId::from(1)]);
+    } else {
+        assert!(timings.passes.is_empty());
+    }
+}
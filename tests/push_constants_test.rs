@@ -0,0 +1,103 @@
+use std::sync::OnceLock;
+
+use bevy::{
+    prelude::*,
+    render::{
+        render_resource::{BindGroupLayout, BindGroupLayoutEntries, binding_types::storage_buffer},
+        renderer::RenderDevice,
+    },
+};
+use bevy_app_compute::{error::Error, prelude::*};
+use wgpu::{PushConstantRange, ShaderStages};
+
+mod common;
+
+// wgpu doesn't derive push constants, so the pipeline layout must be explicit.
+// Layouts belong to the device of the app, this file must only build one app.
+static LAYOUTS: OnceLock<Vec<BindGroupLayout>> = OnceLock::new();
+
+#[derive(TypePath)]
+struct OffsetShader;
+
+impl ComputeShader for OffsetShader {
+    fn shader() -> ShaderRef {
+        "shaders/push_constants.wgsl".into()
+    }
+
+    fn layouts<'a>() -> &'a [BindGroupLayout] {
+        LAYOUTS.get().map_or(&[], Vec::as_slice)
+    }
+
+    fn push_constant_ranges<'a>() -> &'a [PushConstantRange] {
+        &[PushConstantRange {
+            stages: ShaderStages::COMPUTE,
+            range: 0..4,
+        }]
+    }
+}
+
+#[derive(Resource)]
+struct PushConstantsComputeWorker;
+
+impl ComputeWorker for PushConstantsComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        let render_device = world.resource::<RenderDevice>();
+        LAYOUTS.get_or_init(|| {
+            vec![render_device.create_bind_group_layout(
+                "values",
+                &BindGroupLayoutEntries::single(
+                    ShaderStages::COMPUTE,
+                    storage_buffer::<Vec<f32>>(false),
+                ),
+            )]
+        });
+
+        AppComputeWorkerBuilder::new(world)
+            .add_staging("first", &[1f32, 2., 3., 4.])
+            .add_staging("second", &[1f32, 2., 3., 4.])
+            .add_pass::<OffsetShader>([4, 1, 1], &["first"])
+            .set_push_constants(&1f32)
+            .add_pass::<OffsetShader>([4, 1, 1], &["second"])
+            .label("second")
+            .set_push_constants(&10f32)
+            .one_shot()
+            .build()
+    }
+}
+
+fn execute(app: &mut App) {
+    app.world_mut()
+        .resource_mut::<AppComputeWorker<PushConstantsComputeWorker>>()
+        .execute();
+    for _ in 0..5 {
+        app.update();
+    }
+}
+
+#[test]
+fn sets_push_constants_per_pass() {
+    let mut app = common::setup_app::<PushConstantsComputeWorker>();
+    // Let the pipeline compile, one-shot workers don't run on their own.
+    for _ in 0..5 {
+        app.update();
+    }
+
+    execute(&mut app);
+    let mut worker = app
+        .world_mut()
+        .resource_mut::<AppComputeWorker<PushConstantsComputeWorker>>();
+    assert_eq!(worker.read_vec::<f32>("first"), [2., 3., 4., 5.]);
+    assert_eq!(worker.read_vec::<f32>("second"), [11., 12., 13., 14.]);
+
+    assert!(matches!(
+        worker.try_set_push_constants("second", &[1f32, 2.]),
+        Err(Error::PushConstantsMismatch { size: 8, .. })
+    ));
+    worker.set_push_constants("second", &100f32);
+    execute(&mut app);
+    let worker = app
+        .world()
+        .resource::<AppComputeWorker<PushConstantsComputeWorker>>();
+    assert_eq!(worker.read_vec::<f32>("first"), [3., 4., 5., 6.]);
+    assert_eq!(worker.read_vec::<f32>("second"), [111., 112., 113., 114.]);
+}
@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+use bevy_app_compute::{error::Error, prelude::*};
+
+mod common;
+
+#[derive(TypePath)]
+struct SimpleShader;
+
+impl ComputeShader for SimpleShader {
+    fn shader() -> ShaderRef {
+        "shaders/simple.wgsl".into()
+    }
+}
+
+#[derive(Resource)]
+struct OneShotComputeWorker;
+
+impl ComputeWorker for OneShotComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_uniform("uni", &1f32)
+            .add_staging("values", &[1f32, 2., 3., 4.])
+            .add_pass::<SimpleShader>([4, 1, 1], &["uni", "values"])
+            .one_shot()
+            .build()
+    }
+}
+
+fn worker(app: &mut App) -> Mut<'_, AppComputeWorker<OneShotComputeWorker>> {
+    app.world_mut()
+        .resource_mut::<AppComputeWorker<OneShotComputeWorker>>()
+}
+
+#[test]
+fn writes_and_reads_ranges() {
+    let mut app = common::setup_app::<OneShotComputeWorker>();
+
+    // Let the pipeline compile, one-shot workers don't run on their own.
+    for _ in 0..5 {
+        app.update();
+    }
+
+    worker(&mut app).write_range("values", 1..3, &[10f32, 20.]);
+    worker(&mut app).write_at("values", 12, &[30f32]);
+    worker(&mut app).execute();
+    app.update();
+
+    let worker = worker(&mut app);
+    assert!(worker.ready());
+    assert_eq!(worker.read_range::<f32>("values", 1..4), [11., 21., 31.]);
+    assert_eq!(worker.read_range::<f32>("values", 0..1), [2.]);
+}
+
+#[test]
+fn rejects_out_of_bounds_ranges() {
+    let mut app = common::setup_app::<OneShotComputeWorker>();
+    let mut worker = worker(&mut app);
+
+    assert!(matches!(
+        worker.try_write_at("values", 2, &[1f32]),
+        Err(Error::UnalignedWrite {
+            start: 2,
+            end: 6,
+            ..
+        })
+    ));
+    assert!(matches!(
+        worker.try_write_range("values", 3..5, &[1f32, 2.]),
+        Err(Error::OutOfBounds {
+            start: 12,
+            end: 20,
+            ..
+        })
+    ));
+    assert!(matches!(
+        worker.try_write_range("values", 0..2, &[1f32]),
+        Err(Error::RangeLengthMismatch {
+            range: 2,
+            data: 1,
+            ..
+        })
+    ));
+    assert!(matches!(
+        worker.try_write_range("values", usize::MAX - 1..usize::MAX, &[1f32]),
+        Err(Error::RangeOverflow(_))
+    ));
+    assert!(matches!(
+        worker.try_write_at("values", u64::MAX, &[1f32]),
+        Err(Error::RangeOverflow(_))
+    ));
+    assert!(matches!(
+        worker.try_write_range("values", 0..1, &[()]),
+        Err(Error::ZeroSizedType(_))
+    ));
+
+    // Staging buffers can only be read once a run copied to them.
+    for _ in 0..5 {
+        app.update();
+    }
+    app.world_mut()
+        .resource_mut::<AppComputeWorker<OneShotComputeWorker>>()
+        .execute();
+    app.update();
+
+    let worker = app
+        .world()
+        .resource::<AppComputeWorker<OneShotComputeWorker>>();
+    assert!(worker.ready());
+    assert!(matches!(
+        worker.try_read_range::<f32>("values", 2..5),
+        Err(Error::OutOfBounds {
+            start: 8,
+            end: 20,
+            ..
+        })
+    ));
+    assert!(matches!(
+        worker.try_read_range::<()>("values", 0..1),
+        Err(Error::ZeroSizedType(_))
+    ));
+}
@@ -0,0 +1,137 @@
+use bevy::prelude::*;
+use bevy_app_compute::{error::Error, prelude::*};
+
+mod common;
+
+#[derive(TypePath)]
+struct SimpleShader;
+
+impl ComputeShader for SimpleShader {
+    fn shader() -> ShaderRef {
+        "shaders/simple.wgsl".into()
+    }
+}
+
+#[derive(Resource)]
+struct OnRequestComputeWorker;
+
+impl ComputeWorker for OnRequestComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_uniform("uni", &1f32)
+            .add_staging("values", &[1f32, 2., 3., 4.])
+            .set_readback("values", ReadbackPolicy::OnRequest)
+            .add_pass::<SimpleShader>([4, 1, 1], &["uni", "values"])
+            .one_shot()
+            .build()
+    }
+}
+
+#[derive(Resource)]
+struct RangeComputeWorker;
+
+impl ComputeWorker for RangeComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_uniform("uni", &1f32)
+            .add_staging("values", &[1f32, 2., 3., 4.])
+            .set_readback_range("values", 4..12)
+            .add_pass::<SimpleShader>([4, 1, 1], &["uni", "values"])
+            .one_shot()
+            .build()
+    }
+}
+
+fn run_once<W: ComputeWorker>(app: &mut App) {
+    app.world_mut()
+        .resource_mut::<AppComputeWorker<W>>()
+        .execute();
+    app.update();
+    assert!(app.world().resource::<AppComputeWorker<W>>().ready());
+}
+
+#[test]
+fn reads_back_on_request() {
+    let mut app = common::setup_app::<OnRequestComputeWorker>();
+
+    // Let the pipeline compile, one-shot workers don't run on their own.
+    for _ in 0..5 {
+        app.update();
+    }
+
+    // Nothing was copied to the staging buffer yet.
+    run_once::<OnRequestComputeWorker>(&mut app);
+    let worker = app
+        .world()
+        .resource::<AppComputeWorker<OnRequestComputeWorker>>();
+    assert!(matches!(
+        worker.try_read_vec::<f32>("values"),
+        Err(Error::StagingBufferNotReady(_))
+    ));
+
+    app.world_mut()
+        .resource_mut::<AppComputeWorker<OnRequestComputeWorker>>()
+        .request_readback("values");
+    run_once::<OnRequestComputeWorker>(&mut app);
+    let worker = app
+        .world()
+        .resource::<AppComputeWorker<OnRequestComputeWorker>>();
+    assert_eq!(worker.read_vec::<f32>("values"), [3., 4., 5., 6.]);
+
+    // The request only applies to one run.
+    run_once::<OnRequestComputeWorker>(&mut app);
+    let worker = app
+        .world()
+        .resource::<AppComputeWorker<OnRequestComputeWorker>>();
+    assert_eq!(worker.read_vec::<f32>("values"), [3., 4., 5., 6.]);
+}
+
+#[test]
+fn reads_back_a_range() {
+    let mut app = common::setup_app::<RangeComputeWorker>();
+
+    // Let the pipeline compile, one-shot workers don't run on their own.
+    for _ in 0..5 {
+        app.update();
+    }
+
+    run_once::<RangeComputeWorker>(&mut app);
+    let worker = app
+        .world()
+        .resource::<AppComputeWorker<RangeComputeWorker>>();
+    assert_eq!(worker.read_vec::<f32>("values"), [3., 4.]);
+}
+
+#[test]
+fn rejects_invalid_readback_options() {
+    let mut app = common::setup_app::<RangeComputeWorker>();
+
+    let mut builder = AppComputeWorkerBuilder::<RangeComputeWorker>::new(app.world_mut());
+    builder
+        .add_uniform("uni", &1f32)
+        .add_staging("values", &[1f32, 2., 3., 4.])
+        .set_readback("uni", ReadbackPolicy::OnRequest)
+        .set_readback_range("values", 2..8)
+        .set_readback_range("values", 8..20);
+
+    let Err(errors) = builder.try_build() else {
+        panic!("The worker shouldn't build.");
+    };
+
+    assert_eq!(
+        errors,
+        [
+            BuildError::NotStaging("uni".to_owned()),
+            BuildError::InvalidReadbackRange {
+                name: "values".to_owned(),
+                start: 2,
+                end: 8,
+            },
+            BuildError::InvalidReadbackRange {
+                name: "values".to_owned(),
+                start: 8,
+                end: 20,
+            },
+        ]
+    );
+}
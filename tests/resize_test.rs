@@ -0,0 +1,117 @@
+use bevy::prelude::*;
+use bevy_app_compute::prelude::*;
+
+mod common;
+
+#[derive(TypePath)]
+struct SimpleShader;
+
+impl ComputeShader for SimpleShader {
+    fn shader() -> ShaderRef {
+        "shaders/simple.wgsl".into()
+    }
+}
+
+#[derive(Resource)]
+struct OneShotComputeWorker;
+
+impl ComputeWorker for OneShotComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_uniform("uni", &1f32)
+            .add_staging("values", &[1f32, 2., 3., 4.])
+            .add_pass::<SimpleShader>([4, 1, 1], &["uni", "values"])
+            .one_shot()
+            .build()
+    }
+}
+
+#[derive(Resource)]
+struct SwapComputeWorker;
+
+impl ComputeWorker for SwapComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_uniform("uni", &1f32)
+            .add_staging("values", &[1f32, 2., 3., 4.])
+            .add_staging("previous", &[0f32; 4])
+            .add_pass::<SimpleShader>([4, 1, 1], &["uni", "values"])
+            .add_swap("values", "previous")
+            .one_shot()
+            .build()
+    }
+}
+
+fn worker(app: &mut App) -> Mut<'_, AppComputeWorker<OneShotComputeWorker>> {
+    app.world_mut()
+        .resource_mut::<AppComputeWorker<OneShotComputeWorker>>()
+}
+
+fn setup() -> App {
+    let mut app = common::setup_app::<OneShotComputeWorker>();
+
+    // Let the pipeline compile, one-shot workers don't run on their own.
+    for _ in 0..5 {
+        app.update();
+    }
+
+    app
+}
+
+fn run_once(app: &mut App) -> Vec<f32> {
+    worker(app).execute();
+    app.update();
+
+    let worker = worker(app);
+    assert!(worker.ready());
+    worker.read_vec("values")
+}
+
+#[test]
+fn resizes_buffers() {
+    let mut app = setup();
+
+    worker(&mut app).resize_buffer("values", 32, true);
+    assert_eq!(run_once(&mut app), [2., 3., 4., 5., 0., 0., 0., 0.]);
+
+    worker(&mut app).resize_buffer("values", 16, false);
+    assert_eq!(run_once(&mut app), [1., 1., 1., 1.]);
+}
+
+#[test]
+fn grows_buffers_on_write() {
+    let mut app = setup();
+
+    let mut worker = worker(&mut app);
+    worker.write_vec("values", &[1f32, 2., 3., 4., 5., 6., 7., 8.]);
+    worker.set_workgroups(0, [8, 1, 1]);
+
+    assert_eq!(run_once(&mut app), [2., 3., 4., 5., 6., 7., 8., 9.]);
+}
+
+#[test]
+fn resizes_swapped_buffers_alike() {
+    let mut app = common::setup_app::<SwapComputeWorker>();
+
+    // Let the pipeline compile, one-shot workers don't run on their own.
+    for _ in 0..5 {
+        app.update();
+    }
+
+    let mut worker = app
+        .world_mut()
+        .resource_mut::<AppComputeWorker<SwapComputeWorker>>();
+    worker.resize_buffer("values", 32, true);
+    worker.execute();
+    app.update();
+
+    let worker = app
+        .world()
+        .resource::<AppComputeWorker<SwapComputeWorker>>();
+    assert!(worker.ready());
+    assert_eq!(
+        worker.read_vec::<f32>("previous"),
+        [2., 3., 4., 5., 0., 0., 0., 0.]
+    );
+    assert_eq!(worker.read_vec::<f32>("values"), [0.; 8]);
+}
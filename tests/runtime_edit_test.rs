@@ -0,0 +1,141 @@
+use bevy::{prelude::*, render::render_resource::BufferUsages};
+use bevy_app_compute::{error::Error, prelude::*};
+
+mod common;
+
+#[derive(TypePath)]
+struct SimpleShader;
+
+impl ComputeShader for SimpleShader {
+    fn shader() -> ShaderRef {
+        "shaders/simple.wgsl".into()
+    }
+}
+
+#[derive(Resource)]
+struct OneShotComputeWorker;
+
+impl ComputeWorker for OneShotComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_uniform("uni", &1f32)
+            .add_staging("values", &[1f32, 2., 3., 4.])
+            .add_pass::<SimpleShader>([4, 1, 1], &["uni", "values"])
+            .one_shot()
+            .build()
+    }
+}
+
+#[derive(Resource)]
+struct VertexComputeWorker;
+
+impl ComputeWorker for VertexComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .set_extra_buffer_usages(Some(BufferUsages::VERTEX))
+            .add_storage("values", &[1f32, 2., 3., 4.])
+            .build()
+    }
+}
+
+fn worker(app: &mut App) -> Mut<'_, AppComputeWorker<OneShotComputeWorker>> {
+    app.world_mut()
+        .resource_mut::<AppComputeWorker<OneShotComputeWorker>>()
+}
+
+fn setup() -> App {
+    let mut app = common::setup_app::<OneShotComputeWorker>();
+
+    // Let the pipeline compile, one-shot workers don't run on their own.
+    for _ in 0..5 {
+        app.update();
+    }
+
+    app
+}
+
+fn run_once(app: &mut App) {
+    worker(app).execute();
+    app.update();
+    assert!(worker(app).ready());
+}
+
+#[test]
+fn edits_steps_and_buffers() {
+    let mut app = setup();
+
+    let mut worker_mut = worker(&mut app);
+    worker_mut.add_staging("more", &[10f32, 20., 30., 40.]);
+    worker_mut.add_buffer("step", BufferKind::Uniform, &2f32);
+    worker_mut.insert_pass::<SimpleShader>(1, [4, 1, 1], &["step", "more"]);
+    worker_mut.label_step(1, "more");
+    run_once(&mut app);
+
+    let worker_ref = worker(&mut app);
+    assert_eq!(worker_ref.read_vec::<f32>("values"), [2., 3., 4., 5.]);
+    assert_eq!(worker_ref.read_vec::<f32>("more"), [12., 22., 32., 42.]);
+
+    worker(&mut app).set_step_enabled(0, false);
+    run_once(&mut app);
+
+    let worker_ref = worker(&mut app);
+    assert_eq!(worker_ref.read_vec::<f32>("values"), [2., 3., 4., 5.]);
+    assert_eq!(worker_ref.read_vec::<f32>("more"), [14., 24., 34., 44.]);
+
+    let mut worker_mut = worker(&mut app);
+    worker_mut.remove_step("more");
+    worker_mut.remove_buffer("more");
+    worker_mut.set_step_enabled(0, true);
+    run_once(&mut app);
+
+    assert_eq!(worker(&mut app).read_vec::<f32>("values"), [3., 4., 5., 6.]);
+}
+
+#[test]
+fn rejects_invalid_edits() {
+    let mut app = setup();
+    let mut worker = worker(&mut app);
+
+    assert!(matches!(
+        worker.try_add_buffer("values", BufferKind::Uniform, &1f32),
+        Err(Error::DuplicateName(name)) if name == "values"
+    ));
+    assert!(matches!(
+        worker.try_insert_pass::<SimpleShader>(0, [4, 1, 1], &["uni", "missing"]),
+        Err(Error::UnknownResource { name, .. }) if name == "missing"
+    ));
+    assert!(matches!(
+        worker.try_insert_pass::<SimpleShader>(2, [4, 1, 1], &["uni", "values"]),
+        Err(Error::StepNotFound(_))
+    ));
+    assert!(matches!(
+        worker.try_remove_buffer("values"),
+        Err(Error::ResourceInUse { step, .. }) if step == "#0"
+    ));
+    assert!(matches!(
+        worker.try_remove_step(1),
+        Err(Error::StepNotFound(_))
+    ));
+
+    worker.insert_pass::<SimpleShader>(1, [4, 1, 1], &["uni", "values"]);
+    worker.label_step(0, "first");
+    assert!(matches!(
+        worker.try_label_step(1, "first"),
+        Err(Error::DuplicateLabel(label)) if label == "first"
+    ));
+}
+
+#[test]
+fn adds_buffers_with_the_extra_usages_of_the_worker() {
+    let mut app = common::setup_app::<VertexComputeWorker>();
+    let mut worker = app
+        .world_mut()
+        .resource_mut::<AppComputeWorker<VertexComputeWorker>>();
+
+    worker.add_buffer("more", BufferKind::Storage { read_only: false }, &[0f32; 4]);
+
+    for name in ["values", "more"] {
+        let usage = worker.get_buffer(name).unwrap().usage();
+        assert!(usage.contains(BufferUsages::VERTEX | BufferUsages::STORAGE));
+    }
+}
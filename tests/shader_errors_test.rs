@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+use bevy_app_compute::{error::Error, prelude::*};
+
+mod common;
+
+#[derive(TypePath)]
+struct BrokenShader;
+
+impl ComputeShader for BrokenShader {
+    fn shader() -> ShaderRef {
+        "shaders/broken.wgsl".into()
+    }
+}
+
+#[derive(Resource)]
+struct BrokenComputeWorker;
+
+impl ComputeWorker for BrokenComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_staging("values", &[1., 2., 3., 4.])
+            .add_pass::<BrokenShader>([4, 1, 1], &["values"])
+            .build()
+    }
+}
+
+#[derive(Resource)]
+struct BrokenVariantsComputeWorker;
+
+impl ComputeWorker for BrokenVariantsComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_staging("values", &[1., 2., 3., 4.])
+            .add_pass::<BrokenShader>([4, 1, 1], &["values"])
+            .add_pass_with_constants::<BrokenShader>([4, 1, 1], &["values"], &[("SCALE", 2.)])
+            .build()
+    }
+}
+
+#[derive(Resource, Default)]
+struct FailedShaders(Vec<String>);
+
+fn record_failed_shaders(
+    mut events: EventReader<ComputeWorkerError<BrokenComputeWorker>>,
+    mut failed_shaders: ResMut<FailedShaders>,
+) {
+    for event in events.read() {
+        if let Error::PipelineFailed { shader, .. } = &event.error {
+            failed_shaders.0.push(shader.clone());
+        }
+    }
+}
+
+#[test]
+fn reports_shaders_failing_to_compile() {
+    let mut app = common::setup_app::<BrokenComputeWorker>();
+    app.init_resource::<FailedShaders>()
+        .add_systems(Last, record_failed_shaders);
+
+    for _ in 0..10 {
+        app.update();
+    }
+
+    let worker = app
+        .world()
+        .resource::<AppComputeWorker<BrokenComputeWorker>>();
+    assert_eq!(*worker.state(), WorkerState::Failed);
+
+    let shader_errors = worker.shader_errors().collect::<Vec<_>>();
+    assert_eq!(shader_errors.len(), 1);
+    assert_eq!(shader_errors[0].0, BrokenShader::type_path());
+    assert!(shader_errors[0].1.contains("undefined_function"));
+
+    assert_eq!(
+        app.world().resource::<FailedShaders>().0,
+        [BrokenShader::type_path()]
+    );
+}
+
+#[test]
+fn reports_the_pipeline_state_of_passes() {
+    let mut app = common::setup_app::<BrokenComputeWorker>();
+
+    let worker = app
+        .world()
+        .resource::<AppComputeWorker<BrokenComputeWorker>>();
+    assert_eq!(worker.pipeline_state(0), PipelineState::Queued);
+    assert_eq!(worker.pipeline_error(0), None);
+
+    for _ in 0..10 {
+        app.update();
+    }
+
+    let worker = app
+        .world()
+        .resource::<AppComputeWorker<BrokenComputeWorker>>();
+    assert_eq!(worker.pipeline_state(0), PipelineState::Failed);
+    assert!(
+        worker
+            .pipeline_error(0)
+            .is_some_and(|message| message.contains("undefined_function"))
+    );
+    assert!(matches!(
+        worker.try_pipeline_state(1),
+        Err(Error::StepNotFound(_))
+    ));
+}
+
+#[test]
+fn lists_shaders_failing_with_several_constant_sets_once() {
+    let mut app = common::setup_app::<BrokenVariantsComputeWorker>();
+
+    for _ in 0..10 {
+        app.update();
+    }
+
+    let worker = app
+        .world()
+        .resource::<AppComputeWorker<BrokenVariantsComputeWorker>>();
+    assert_eq!(*worker.state(), WorkerState::Failed);
+
+    let shader_errors = worker.shader_errors().collect::<Vec<_>>();
+    assert_eq!(shader_errors.len(), 1);
+    assert_eq!(shader_errors[0].0, BrokenShader::type_path());
+}
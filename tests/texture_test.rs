@@ -0,0 +1,146 @@
+use bevy::{
+    prelude::*,
+    render::render_resource::{Extent3d, StorageTextureAccess, TextureDimension, TextureFormat},
+};
+use bevy_app_compute::prelude::*;
+
+mod common;
+
+// Not a multiple of 64 texels, so readback rows are padded.
+const SIZE: Extent3d = Extent3d {
+    width: 5,
+    height: 3,
+    depth_or_array_layers: 1,
+};
+
+#[derive(TypePath)]
+struct TextureShader;
+
+impl ComputeShader for TextureShader {
+    fn shader() -> ShaderRef {
+        "shaders/texture.wgsl".into()
+    }
+}
+
+#[derive(Resource)]
+struct TextureComputeWorker;
+
+impl ComputeWorker for TextureComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        let input: Vec<u32> = (0..SIZE.width * SIZE.height).collect();
+
+        AppComputeWorkerBuilder::new(world)
+            .add_texture(
+                "input",
+                SIZE,
+                TextureDimension::D2,
+                TextureFormat::R32Uint,
+                bytemuck::cast_slice(&input),
+            )
+            .add_staging_texture(
+                "output",
+                SIZE,
+                TextureDimension::D2,
+                TextureFormat::R32Uint,
+                StorageTextureAccess::WriteOnly,
+            )
+            .add_pass::<TextureShader>([SIZE.width, SIZE.height, 1], &["input", "output"])
+            .build()
+    }
+}
+
+#[derive(Resource)]
+struct ReadOnlyComputeWorker;
+
+impl ComputeWorker for ReadOnlyComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_texture(
+                "input",
+                SIZE,
+                TextureDimension::D2,
+                TextureFormat::R32Uint,
+                bytemuck::cast_slice(&[0u32; (SIZE.width * SIZE.height) as usize]),
+            )
+            .add_staging_texture(
+                "output",
+                SIZE,
+                TextureDimension::D2,
+                TextureFormat::R32Uint,
+                StorageTextureAccess::WriteOnly,
+            )
+            .add_staging_texture(
+                "read_only",
+                SIZE,
+                TextureDimension::D2,
+                TextureFormat::R32Uint,
+                StorageTextureAccess::ReadOnly,
+            )
+            .add_pass::<TextureShader>([SIZE.width, SIZE.height, 1], &["input", "output"])
+            .build()
+    }
+}
+
+#[test]
+fn reads_back_textures_without_padding() {
+    fn test(compute_worker: ResMut<AppComputeWorker<TextureComputeWorker>>) {
+        let result: Vec<u32> = compute_worker.read_texture("output");
+        let expected: Vec<u32> = (0..SIZE.width * SIZE.height).map(|v| v * 2).collect();
+        assert_eq!(result, expected);
+    }
+
+    let mut app = common::build_app::<TextureComputeWorker>();
+    app.add_systems(Update, test);
+    app.update();
+}
+
+#[test]
+fn reads_back_read_only_staging_textures() {
+    let app = common::build_app::<ReadOnlyComputeWorker>();
+
+    let worker = app
+        .world()
+        .resource::<AppComputeWorker<ReadOnlyComputeWorker>>();
+    let result: Vec<u32> = worker.read_texture("read_only");
+    assert_eq!(result, [0; (SIZE.width * SIZE.height) as usize]);
+}
+
+#[test]
+fn rejects_staging_textures_of_unsupported_formats() {
+    let mut app = common::setup_app::<TextureComputeWorker>();
+
+    let mut builder = AppComputeWorkerBuilder::<TextureComputeWorker>::new(app.world_mut());
+    builder
+        .add_staging_texture(
+            "depth",
+            SIZE,
+            TextureDimension::D2,
+            TextureFormat::Depth24PlusStencil8,
+            StorageTextureAccess::WriteOnly,
+        )
+        .add_staging_texture(
+            "compressed",
+            SIZE,
+            TextureDimension::D2,
+            TextureFormat::Bc1RgbaUnorm,
+            StorageTextureAccess::WriteOnly,
+        );
+
+    let Err(errors) = builder.try_build() else {
+        panic!("The worker shouldn't build.");
+    };
+
+    assert_eq!(
+        errors,
+        [
+            BuildError::UnsupportedStagingFormat {
+                name: "depth".to_owned(),
+                format: TextureFormat::Depth24PlusStencil8,
+            },
+            BuildError::UnsupportedStagingFormat {
+                name: "compressed".to_owned(),
+                format: TextureFormat::Bc1RgbaUnorm,
+            },
+        ]
+    );
+}
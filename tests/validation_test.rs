@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+use bevy_app_compute::prelude::*;
+
+mod common;
+
+#[derive(TypePath)]
+struct GroupsShader;
+
+impl ComputeShader for GroupsShader {
+    fn shader() -> ShaderRef {
+        "shaders/groups.wgsl".into()
+    }
+}
+
+#[derive(Resource)]
+struct StorageAsUniformComputeWorker;
+
+impl ComputeWorker for StorageAsUniformComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .strict()
+            .add_rw_storage("scale", &2f32)
+            .add_staging("values", &[1., 2., 3., 4.])
+            .add_pass_with_bindings::<GroupsShader>([4, 1, 1], &[(0, 0, "scale"), (1, 0, "values")])
+            .build()
+    }
+}
+
+#[derive(Resource)]
+struct ReadOnlyStorageComputeWorker;
+
+impl ComputeWorker for ReadOnlyStorageComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .strict()
+            .add_uniform("scale", &2f32)
+            .add_storage("values", &[1., 2., 3., 4.])
+            .add_pass_with_bindings::<GroupsShader>([4, 1, 1], &[(0, 0, "scale"), (1, 0, "values")])
+            .label("scale values")
+            .build()
+    }
+}
+
+#[derive(Resource)]
+struct LenientReadOnlyStorageComputeWorker;
+
+impl ComputeWorker for LenientReadOnlyStorageComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_uniform("scale", &2f32)
+            .add_storage("values", &[1., 2., 3., 4.])
+            .add_pass_with_bindings::<GroupsShader>([4, 1, 1], &[(0, 0, "scale"), (1, 0, "values")])
+            .build()
+    }
+}
+
+#[derive(Resource)]
+struct TooSmallComputeWorker;
+
+impl ComputeWorker for TooSmallComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .strict()
+            .add_uniform("scale", &2f32)
+            .add_empty_rw_storage("values", 0)
+            .add_pass_with_bindings::<GroupsShader>([4, 1, 1], &[(0, 0, "scale"), (1, 0, "values")])
+            .build()
+    }
+}
+
+#[test]
+#[should_panic(expected = "BindingMismatch")]
+fn reports_storage_buffer_bound_as_uniform() {
+    common::build_app::<StorageAsUniformComputeWorker>();
+}
+
+#[test]
+#[should_panic(expected = "read-only storage buffer `values`")]
+fn reports_read_only_buffer_bound_as_read_write() {
+    common::build_app::<ReadOnlyStorageComputeWorker>();
+}
+
+#[test]
+fn only_warns_about_read_only_buffers_bound_as_read_write() {
+    let app = common::build_app::<LenientReadOnlyStorageComputeWorker>();
+
+    let worker = app
+        .world()
+        .resource::<AppComputeWorker<LenientReadOnlyStorageComputeWorker>>();
+    assert!(worker.ready());
+}
+
+#[test]
+#[should_panic(expected = "of 0 bytes")]
+fn reports_buffers_too_small_for_the_shader() {
+    common::build_app::<TooSmallComputeWorker>();
+}
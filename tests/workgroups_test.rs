@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+use bevy_app_compute::prelude::*;
+
+mod common;
+
+#[derive(TypePath)]
+struct DoubleShader;
+
+impl ComputeShader for DoubleShader {
+    fn shader() -> ShaderRef {
+        "shaders/workgroups.wgsl".into()
+    }
+}
+
+#[derive(Resource)]
+struct Count(u32);
+
+#[derive(Resource)]
+struct DynamicComputeWorker;
+
+impl ComputeWorker for DynamicComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_storage("input", &[1., 2., 3., 4.])
+            .add_staging("output", &[0f32; 4])
+            .add_pass_dynamic::<DoubleShader>(
+                |world| {
+                    [
+                        world.get_resource::<Count>().map_or(0, |count| count.0),
+                        1,
+                        1,
+                    ]
+                },
+                &["input", "output"],
+            )
+            .build()
+    }
+}
+
+#[derive(Resource)]
+struct LabeledComputeWorker;
+
+impl ComputeWorker for LabeledComputeWorker {
+    fn build(world: &mut World) -> AppComputeWorker<Self> {
+        AppComputeWorkerBuilder::new(world)
+            .add_storage("input", &[1., 2., 3., 4.])
+            .add_staging("output", &[0f32; 4])
+            .add_pass::<DoubleShader>([1, 1, 1], &["input", "output"])
+            .label("double")
+            .build()
+    }
+}
+
+#[test]
+fn evaluates_dynamic_workgroups_every_run() {
+    let mut app = common::build_app::<DynamicComputeWorker>();
+    let result: Vec<f32> = app
+        .world()
+        .resource::<AppComputeWorker<DynamicComputeWorker>>()
+        .read_vec("output");
+    assert_eq!(result, [0., 0., 0., 0.]);
+
+    app.insert_resource(Count(2));
+    app.update();
+
+    let result: Vec<f32> = app
+        .world()
+        .resource::<AppComputeWorker<DynamicComputeWorker>>()
+        .read_vec("output");
+    assert_eq!(result, [2., 4., 0., 0.]);
+}
+
+#[test]
+fn sets_workgroups_by_label_and_index() {
+    let mut app = common::build_app::<LabeledComputeWorker>();
+
+    let mut worker = app
+        .world_mut()
+        .resource_mut::<AppComputeWorker<LabeledComputeWorker>>();
+    worker.set_workgroups("double", [3, 1, 1]);
+    assert!(worker.try_set_workgroups("missing", [4, 1, 1]).is_err());
+    assert!(worker.try_set_workgroups(1, [4, 1, 1]).is_err());
+    app.update();
+
+    let result: Vec<f32> = app
+        .world()
+        .resource::<AppComputeWorker<LabeledComputeWorker>>()
+        .read_vec("output");
+    assert_eq!(result, [2., 4., 6., 0.]);
+
+    app.world_mut()
+        .resource_mut::<AppComputeWorker<LabeledComputeWorker>>()
+        .set_workgroups(0, [4, 1, 1]);
+    app.update();
+
+    let result: Vec<f32> = app
+        .world()
+        .resource::<AppComputeWorker<LabeledComputeWorker>>()
+        .read_vec("output");
+    assert_eq!(result, [2., 4., 6., 8.]);
+}